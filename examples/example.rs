@@ -23,10 +23,17 @@ impl Square {
     }
 }
 impl Draggable for Square {
-    fn draw(&self, context: &Context, x: f64, y: f64) -> Result<(), Error> {
+    fn draw(&self, context: &Context, x: f64, y: f64, state: DrawState) -> Result<(), Error> {
         context.set_source_rgb(self.r, self.g, self.b);
         context.rectangle(x, y, 100.0, 100.0);
-        context.fill()?;
+        if state.selected {
+            context.fill_preserve()?;
+            context.set_source_rgb(0.0, 0.0, 0.0);
+            context.set_line_width(4.0);
+            context.stroke()?;
+        } else {
+            context.fill()?;
+        }
         Ok(())
     }
     fn get_limits(&self) -> (f64, f64, f64, f64) {
@@ -35,17 +42,25 @@ impl Draggable for Square {
     fn retain(&self) -> bool {
         self.retain_flag.get()
     }
-    fn on_middle_click(&self) {
+    fn on_middle_click(&self, _event: &PointerEvent) -> glib::Propagation {
         println!("square middle clicked - removing");
         self.retain_flag.set(false);
+        glib::Propagation::Stop
     }
 }
 struct Circle(f64, f64, f64);
 impl Draggable for Circle {
-    fn draw(&self, context: &Context, x: f64, y: f64) -> Result<(), Error> {
+    fn draw(&self, context: &Context, x: f64, y: f64, state: DrawState) -> Result<(), Error> {
         context.set_source_rgb(self.0, self.1, self.2);
         context.arc(x, y, 50.0, 0.0, 6.29);
-        context.fill()?;
+        if state.selected {
+            context.fill_preserve()?;
+            context.set_source_rgb(0.0, 0.0, 0.0);
+            context.set_line_width(4.0);
+            context.stroke()?;
+        } else {
+            context.fill()?;
+        }
         Ok(())
     }
     fn get_limits(&self) -> (f64, f64, f64, f64) {
@@ -54,14 +69,17 @@ impl Draggable for Circle {
     fn contains(&self, x: f64, y: f64) -> bool {
         (x.powi(2) + y.powi(2)).sqrt() <= 50.0
     }
-    fn on_double_click(&self) {
+    fn on_double_click(&self, _event: &PointerEvent) -> glib::Propagation {
         println!("circle double clicked");
+        glib::Propagation::Stop
     }
-    fn on_middle_click(&self) {
+    fn on_middle_click(&self, _event: &PointerEvent) -> glib::Propagation {
         println!("circle middle clicked");
+        glib::Propagation::Stop
     }
-    fn on_right_click(&self) {
+    fn on_right_click(&self, _event: &PointerEvent) -> glib::Propagation {
         println!("circle right clicked");
+        glib::Propagation::Stop
     }
 }
 fn main() -> glib::ExitCode {
@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: BSD-3-Clause
+// Copyright 2024 UxuginPython
+//Geometry for AlignmentGuides. Only the x axis is handled directly; the y axis is handled by
+//transposing everything, aligning on the x axis, and transposing back.
+
+//How close an edge must be to count as aligned when finding guides to draw.
+const EPSILON: f64 = 1e-6;
+#[derive(Clone, Copy)]
+pub struct Bounds {
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+    pub bottom: f64,
+}
+impl Bounds {
+    pub fn new(x: f64, y: f64, limits: (f64, f64, f64, f64)) -> Self {
+        let (neg_x, pos_x, neg_y, pos_y) = limits;
+        Self {
+            left: x - neg_x,
+            right: x + pos_x,
+            top: y - neg_y,
+            bottom: y + pos_y,
+        }
+    }
+    fn center_x(&self) -> f64 {
+        (self.left + self.right) / 2.0
+    }
+    fn center_y(&self) -> f64 {
+        (self.top + self.bottom) / 2.0
+    }
+    fn xs(&self) -> [f64; 3] {
+        [self.left, self.center_x(), self.right]
+    }
+    //Whether the two are in the same "row," i.e., they overlap vertically.
+    fn shares_row(&self, other: &Bounds) -> bool {
+        self.top < other.bottom && self.bottom > other.top
+    }
+    fn transpose(&self) -> Self {
+        Self {
+            left: self.top,
+            right: self.bottom,
+            top: self.left,
+            bottom: self.right,
+        }
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GuideKind {
+    //Edges or centers lining up.
+    Alignment,
+    //One of several equal gaps.
+    Spacing,
+}
+//A line segment to draw in scene coordinates.
+#[derive(Clone, Copy)]
+pub struct Guide {
+    pub kind: GuideKind,
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+}
+impl Guide {
+    fn transpose(&self) -> Self {
+        Self {
+            kind: self.kind,
+            x0: self.y0,
+            y0: self.x0,
+            x1: self.y1,
+            y1: self.x1,
+        }
+    }
+}
+//Returns how far to move `moving` on each axis to align it with `others`, or 0.0 on an axis where
+//nothing is within `threshold`.
+pub fn align(moving: Bounds, others: &[Bounds], threshold: f64, equal_spacing: bool) -> (f64, f64) {
+    let transposed: Vec<Bounds> = others.iter().map(Bounds::transpose).collect();
+    (
+        align_x(moving, others, threshold, equal_spacing).unwrap_or(0.0),
+        align_x(moving.transpose(), &transposed, threshold, equal_spacing).unwrap_or(0.0),
+    )
+}
+//Returns the guides showing how `moving` is aligned with `others` where it is now.
+pub fn guides(moving: Bounds, others: &[Bounds], equal_spacing: bool) -> Vec<Guide> {
+    let transposed: Vec<Bounds> = others.iter().map(Bounds::transpose).collect();
+    let mut output = guides_x(moving, others, equal_spacing);
+    output.extend(
+        guides_x(moving.transpose(), &transposed, equal_spacing)
+            .iter()
+            .map(Guide::transpose),
+    );
+    output
+}
+fn align_x(moving: Bounds, others: &[Bounds], threshold: f64, equal_spacing: bool) -> Option<f64> {
+    let mut best: Option<f64> = None;
+    let mut consider = |offset: f64| {
+        if offset.abs() <= threshold && best.map_or(true, |best| offset.abs() < best.abs()) {
+            best = Some(offset);
+        }
+    };
+    for other in others {
+        for a in moving.xs() {
+            for b in other.xs() {
+                consider(b - a);
+            }
+        }
+    }
+    if equal_spacing {
+        for (left, _) in spacing_targets_x(moving, others) {
+            consider(left - moving.left);
+        }
+    }
+    best
+}
+fn guides_x(moving: Bounds, others: &[Bounds], equal_spacing: bool) -> Vec<Guide> {
+    let mut output = Vec::new();
+    for other in others {
+        for a in moving.xs() {
+            for b in other.xs() {
+                if (a - b).abs() < EPSILON {
+                    output.push(Guide {
+                        kind: GuideKind::Alignment,
+                        x0: b,
+                        y0: moving.top.min(other.top),
+                        x1: b,
+                        y1: moving.bottom.max(other.bottom),
+                    });
+                }
+            }
+        }
+    }
+    if equal_spacing {
+        for (left, guides) in spacing_targets_x(moving, others) {
+            if (left - moving.left).abs() < EPSILON {
+                output.extend(guides);
+            }
+        }
+    }
+    output
+}
+//Returns left coordinates for `moving` that would make gaps between it and the objects beside it
+//equal, each with the guides showing those gaps.
+fn spacing_targets_x(moving: Bounds, others: &[Bounds]) -> Vec<(f64, Vec<Guide>)> {
+    let width = moving.right - moving.left;
+    let y = moving.center_y();
+    let gap = |x0: f64, x1: f64| Guide {
+        kind: GuideKind::Spacing,
+        x0,
+        y0: y,
+        x1,
+        y1: y,
+    };
+    let row: Vec<&Bounds> = others
+        .iter()
+        .filter(|other| other.shares_row(&moving))
+        .collect();
+    //The nearest object whose center is on the given side of `of`.
+    let nearest_left = |of: &Bounds| {
+        row.iter()
+            .filter(|other| other.center_x() < of.center_x())
+            .max_by(|a, b| a.right.total_cmp(&b.right))
+            .copied()
+    };
+    let nearest_right = |of: &Bounds| {
+        row.iter()
+            .filter(|other| other.center_x() > of.center_x())
+            .min_by(|a, b| a.left.total_cmp(&b.left))
+            .copied()
+    };
+    let mut output = Vec::new();
+    let left = nearest_left(&moving);
+    let right = nearest_right(&moving);
+    //Centered between its neighbors.
+    if let (Some(left), Some(right)) = (left, right) {
+        let space = (right.left - left.right - width) / 2.0;
+        if space >= 0.0 {
+            let target = left.right + space;
+            output.push((
+                target,
+                vec![gap(left.right, target), gap(target + width, right.left)],
+            ));
+        }
+    }
+    //Continuing a gap to the left of it.
+    if let Some(left) = left {
+        if let Some(left_2) = nearest_left(left) {
+            let space = left.left - left_2.right;
+            if space >= 0.0 {
+                let target = left.right + space;
+                output.push((
+                    target,
+                    vec![gap(left_2.right, left.left), gap(left.right, target)],
+                ));
+            }
+        }
+    }
+    //Continuing a gap to the right of it.
+    if let Some(right) = right {
+        if let Some(right_2) = nearest_right(right) {
+            let space = right_2.left - right.right;
+            if space >= 0.0 {
+                let target = right.left - space - width;
+                output.push((
+                    target,
+                    vec![
+                        gap(target + width, right.left),
+                        gap(right.right, right_2.left),
+                    ],
+                ));
+            }
+        }
+    }
+    output
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn bounds(left: f64, top: f64, width: f64, height: f64) -> Bounds {
+        Bounds {
+            left,
+            right: left + width,
+            top,
+            bottom: top + height,
+        }
+    }
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("expected an offset");
+        assert!(
+            (actual - expected).abs() < EPSILON,
+            "expected {expected}, got {actual}"
+        );
+    }
+    #[test]
+    fn snaps_edge_to_edge() {
+        let others = [bounds(0.0, 0.0, 100.0, 50.0)];
+        let moving = bounds(103.0, 0.0, 20.0, 50.0);
+        assert_close(align_x(moving, &others, 5.0, false), -3.0);
+    }
+    #[test]
+    fn snaps_center_to_center() {
+        let others = [bounds(0.0, 0.0, 100.0, 50.0)];
+        let moving = bounds(42.0, 100.0, 20.0, 50.0);
+        assert_close(align_x(moving, &others, 5.0, false), -2.0);
+    }
+    #[test]
+    fn rejects_beyond_threshold() {
+        let others = [bounds(0.0, 0.0, 100.0, 50.0)];
+        let moving = bounds(110.0, 0.0, 20.0, 50.0);
+        assert_eq!(align_x(moving, &others, 5.0, false), None);
+        assert_eq!(align_x(moving, &others, 5.0, true), None);
+    }
+    #[test]
+    fn snaps_to_center_between_neighbors() {
+        let others = [
+            bounds(0.0, 0.0, 100.0, 50.0),
+            bounds(200.0, 0.0, 100.0, 50.0),
+        ];
+        let moving = bounds(133.0, 0.0, 40.0, 50.0);
+        let targets = spacing_targets_x(moving, &others);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].0, 130.0);
+        assert_eq!(targets[0].1.len(), 2);
+        assert_close(align_x(moving, &others, 5.0, true), -3.0);
+        assert_eq!(align_x(moving, &others, 5.0, false), None);
+    }
+    #[test]
+    fn continues_gap_to_the_left() {
+        let others = [bounds(0.0, 0.0, 50.0, 50.0), bounds(80.0, 0.0, 50.0, 50.0)];
+        let moving = bounds(162.0, 0.0, 40.0, 50.0);
+        let targets = spacing_targets_x(moving, &others);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].0, 160.0);
+        assert_close(align_x(moving, &others, 5.0, true), -2.0);
+    }
+    #[test]
+    fn continues_gap_to_the_right() {
+        let others = [
+            bounds(100.0, 0.0, 50.0, 50.0),
+            bounds(180.0, 0.0, 50.0, 50.0),
+        ];
+        let moving = bounds(28.0, 0.0, 40.0, 50.0);
+        let targets = spacing_targets_x(moving, &others);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].0, 30.0);
+        assert_close(align_x(moving, &others, 5.0, true), 2.0);
+    }
+    #[test]
+    fn ignores_objects_in_other_rows_for_spacing() {
+        let others = [
+            bounds(0.0, 100.0, 50.0, 50.0),
+            bounds(80.0, 100.0, 50.0, 50.0),
+        ];
+        let moving = bounds(162.0, 0.0, 40.0, 50.0);
+        assert!(spacing_targets_x(moving, &others).is_empty());
+    }
+    #[test]
+    fn aligns_vertically_by_transposing() {
+        let others = [bounds(0.0, 0.0, 50.0, 100.0)];
+        let moving = bounds(0.0, 103.0, 50.0, 20.0);
+        assert_eq!(align(moving, &others, 5.0, false), (0.0, -3.0));
+        let others = [bounds(0.0, 0.0, 50.0, 50.0), bounds(0.0, 80.0, 50.0, 50.0)];
+        let moving = bounds(0.0, 162.0, 50.0, 40.0);
+        assert_eq!(align(moving, &others, 5.0, true), (0.0, -2.0));
+        let guides = guides(bounds(0.0, 160.0, 50.0, 40.0), &others, true);
+        assert!(guides
+            .iter()
+            .any(|guide| guide.kind == GuideKind::Spacing && guide.x0 == guide.x1));
+    }
+}
@@ -1,10 +1,20 @@
 // SPDX-License-Identifier: BSD-3-Clause
 // Copyright 2024 UxuginPython
-use crate::{Context, Draggable};
-use gtk4::{glib, prelude::*, subclass::prelude::*, DrawingArea, GestureClick, GestureDrag};
+use crate::{
+    guides, AlignmentGuides, Constraint, Context, Coordinates, Draggable, DrawState, Error, Grid,
+    Inertia, ItemId, PointerEvent, Rectangle, ScrollLimits, SnapMode, DEFAULT_LAYER,
+};
+use glib::subclass::Signal;
+use gtk4::{
+    gdk, glib, graphene, prelude::*, subclass::prelude::*, Adjustment, DrawingArea,
+    EventControllerKey, EventControllerLegacy, EventControllerMotion, EventControllerScroll,
+    EventControllerScrollFlags, EventSequenceState, GestureClick, GestureDrag, GestureZoom,
+    Orientation, Scrollable, ScrollablePolicy,
+};
 use std::cell::{Cell, RefCell};
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::rc::Rc;
+use std::sync::OnceLock;
 enum Reference<T: ?Sized> {
     Box(Box<T>),
     Rc(Rc<T>),
@@ -47,56 +57,303 @@ impl<T: ?Sized> Deref for ReferenceBorrow<'_, T> {
         }
     }
 }
+//This is an Rc so that callbacks can be run without the DraggableSetHolder borrowed, allowing them
+//to use the DragArea.
+type SharedDraggable = Rc<Reference<dyn Draggable>>;
 struct DraggableAndCoordinates {
-    draggable: Reference<dyn Draggable>,
+    id: ItemId,
+    layer: u64,
+    draggable: SharedDraggable,
     x: f64,
     y: f64,
+    constraint: Option<Constraint>,
 }
 impl DraggableAndCoordinates {
-    fn borrow(&self) -> DraggableBorrowAndCoordinates {
+    fn borrow(&self) -> DraggableBorrowAndCoordinates<'_> {
         DraggableBorrowAndCoordinates {
+            id: self.id,
+            layer: self.layer,
             draggable: self.draggable.borrow(),
             x: self.x,
             y: self.y,
         }
     }
 }
+struct Layer {
+    id: u64,
+    name: String,
+    visible: bool,
+    locked: bool,
+}
 struct DraggableSetHolder {
+    //Objects are kept sorted by the position of their layer in layers so that the objects of each
+    //layer are contiguous and the whole Vec is in drawing order.
     draggables_and_locs: Vec<DraggableAndCoordinates>,
+    //Bottom to top.
+    layers: Vec<Layer>,
+    next_id: u64,
+    next_layer_id: u64,
 }
 impl DraggableSetHolder {
     fn new() -> Self {
         Self {
             draggables_and_locs: Vec::new(),
+            layers: vec![Layer {
+                id: 0,
+                name: DEFAULT_LAYER.to_owned(),
+                visible: true,
+                locked: false,
+            }],
+            next_id: 0,
+            next_layer_id: 1,
         }
     }
-    fn push(&mut self, item: Reference<dyn Draggable>, x: f64, y: f64) {
-        self.draggables_and_locs.push(DraggableAndCoordinates {
-            draggable: item,
-            x: x,
-            y: y,
-        })
+    fn push(&mut self, layer: &str, item: Reference<dyn Draggable>, x: f64, y: f64) -> ItemId {
+        let id = ItemId(self.next_id);
+        self.next_id += 1;
+        let layer = self.layer_id_or_add(layer);
+        let index = self.layer_range(layer).end;
+        self.draggables_and_locs.insert(
+            index,
+            DraggableAndCoordinates {
+                id,
+                layer,
+                draggable: Rc::new(item),
+                x,
+                y,
+                constraint: None,
+            },
+        );
+        id
+    }
+    fn get(&self, id: ItemId) -> Option<&DraggableAndCoordinates> {
+        self.draggables_and_locs
+            .iter()
+            .find(|draggable_and_coords| draggable_and_coords.id == id)
+    }
+    fn get_mut(&mut self, id: ItemId) -> Option<&mut DraggableAndCoordinates> {
+        self.draggables_and_locs
+            .iter_mut()
+            .find(|draggable_and_coords| draggable_and_coords.id == id)
+    }
+    fn get_draggable(&self, id: ItemId) -> Option<SharedDraggable> {
+        self.get(id)
+            .map(|draggable_and_coords| draggable_and_coords.draggable.clone())
+    }
+    fn index_of(&self, id: ItemId) -> Option<usize> {
+        self.draggables_and_locs
+            .iter()
+            .position(|draggable_and_coords| draggable_and_coords.id == id)
     }
     fn iter(&self) -> DraggableSetHolderIterator<'_> {
-        let len = self.draggables_and_locs.len();
-        let index_back = if len >= 1 { len - 1 } else { 0 };
         DraggableSetHolderIterator {
             holder: self, //This is a reference
             index_start: 0,
-            index_back: index_back,
+            index_end: self.draggables_and_locs.len(),
+        }
+    }
+    fn iter_visible(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = DraggableBorrowAndCoordinates<'_>> + '_ {
+        self.iter()
+            .filter(|draggable_and_coords| self.layer_by_id(draggable_and_coords.layer).visible)
+    }
+    //Objects that can be dragged and clicked, i.e., those on visible, unlocked layers.
+    fn iter_interactive(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = DraggableBorrowAndCoordinates<'_>> + '_ {
+        self.iter()
+            .filter(|draggable_and_coords| self.is_interactive(draggable_and_coords.layer))
+    }
+    //Interactive objects containing the given point in scene coordinates, topmost first.
+    fn hits(&self, x: f64, y: f64) -> Vec<&DraggableAndCoordinates> {
+        self.draggables_and_locs
+            .iter()
+            .rev()
+            .filter(|draggable_and_coords| {
+                self.is_interactive(draggable_and_coords.layer)
+                    && draggable_and_coords
+                        .draggable
+                        .borrow()
+                        .contains(x - draggable_and_coords.x, y - draggable_and_coords.y)
+            })
+            .collect()
+    }
+    //Visible objects lying entirely within the given rectangle in scene coordinates, topmost
+    //first.
+    fn in_rect(&self, left: f64, top: f64, right: f64, bottom: f64) -> Vec<ItemId> {
+        self.iter_visible()
+            .rev()
+            .filter(|draggable_and_coords| {
+                let (neg_x, pos_x, neg_y, pos_y) = draggable_and_coords.draggable.get_limits();
+                draggable_and_coords.x - neg_x >= left
+                    && draggable_and_coords.x + pos_x <= right
+                    && draggable_and_coords.y - neg_y >= top
+                    && draggable_and_coords.y + pos_y <= bottom
+            })
+            .map(|draggable_and_coords| draggable_and_coords.id)
+            .collect()
+    }
+    //The selection from a marquee covering the given rectangle in scene coordinates: the
+    //selection from before it was drawn along with the interactive objects within it.
+    fn marquee_selection(
+        &self,
+        base_selection: &[ItemId],
+        left: f64,
+        top: f64,
+        right: f64,
+        bottom: f64,
+    ) -> Vec<ItemId> {
+        let mut selection = base_selection.to_vec();
+        for id in self.in_rect(left, top, right, bottom) {
+            let interactive = self.get(id).is_some_and(|draggable_and_coords| {
+                self.is_interactive(draggable_and_coords.layer)
+            });
+            if interactive && !selection.contains(&id) {
+                selection.push(id);
+            }
         }
+        selection
+    }
+    fn is_interactive(&self, layer: u64) -> bool {
+        let layer = self.layer_by_id(layer);
+        layer.visible && !layer.locked
+    }
+    fn move_to_end(&mut self, index: usize) {
+        let element = self.draggables_and_locs.remove(index);
+        let end = self.layer_range(element.layer).end;
+        self.draggables_and_locs.insert(end, element);
     }
-    fn move_to_end(&mut self, index: usize) -> usize {
+    fn z_index(&self, id: ItemId) -> Option<usize> {
+        let index = self.index_of(id)?;
+        Some(
+            index
+                - self
+                    .layer_range(self.draggables_and_locs[index].layer)
+                    .start,
+        )
+    }
+    //Indices past the end of the layer are treated as the end.
+    fn set_z_index(&mut self, id: ItemId, z_index: usize) -> bool {
+        let index = match self.index_of(id) {
+            Some(index) => index,
+            None => return false,
+        };
         let element = self.draggables_and_locs.remove(index);
-        self.draggables_and_locs.push(element);
-        self.draggables_and_locs.len() - 1
+        let range = self.layer_range(element.layer);
+        let index = range.start + z_index.min(range.len());
+        self.draggables_and_locs.insert(index, element);
+        true
+    }
+    fn remove(&mut self, id: ItemId) -> Option<DraggableAndCoordinates> {
+        let index = self.index_of(id)?;
+        Some(self.draggables_and_locs.remove(index))
+    }
+    fn remove_where(
+        &mut self,
+        mut predicate: impl FnMut(&DraggableAndCoordinates) -> bool,
+    ) -> Vec<DraggableAndCoordinates> {
+        let (removed, kept) = std::mem::take(&mut self.draggables_and_locs)
+            .into_iter()
+            .partition(|draggable_and_coords| predicate(draggable_and_coords));
+        self.draggables_and_locs = kept;
+        removed
+    }
+    fn retain(&mut self) -> Vec<DraggableAndCoordinates> {
+        self.remove_where(|draggable_and_coords| !draggable_and_coords.draggable.borrow().retain())
+    }
+    fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+    fn layer_mut(&mut self, name: &str) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|layer| layer.name == name)
+    }
+    //Every object's layer is in layers, so this only panics if that is somehow not the case.
+    fn layer_by_id(&self, id: u64) -> &Layer {
+        self.layers.iter().find(|layer| layer.id == id).unwrap()
     }
-    fn retain(&mut self) {
+    fn layer_position(&self, id: u64) -> usize {
+        self.layers.iter().position(|layer| layer.id == id).unwrap()
+    }
+    fn layer_range(&self, id: u64) -> Range<usize> {
+        let position = self.layer_position(id);
+        let start = self
+            .draggables_and_locs
+            .iter()
+            .take_while(|draggable_and_coords| {
+                self.layer_position(draggable_and_coords.layer) < position
+            })
+            .count();
+        let len = self.draggables_and_locs[start..]
+            .iter()
+            .take_while(|draggable_and_coords| draggable_and_coords.layer == id)
+            .count();
+        start..start + len
+    }
+    fn add_layer(&mut self, name: &str) -> bool {
+        if self.layer(name).is_some() {
+            return false;
+        }
+        self.layer_id_or_add(name);
+        true
+    }
+    fn layer_id_or_add(&mut self, name: &str) -> u64 {
+        if let Some(layer) = self.layer(name) {
+            return layer.id;
+        }
+        let id = self.next_layer_id;
+        self.next_layer_id += 1;
+        self.layers.push(Layer {
+            id,
+            name: name.to_owned(),
+            visible: true,
+            locked: false,
+        });
+        id
+    }
+    //Returns None if the layer does not exist or is the default layer, which cannot be removed.
+    fn remove_layer(&mut self, name: &str) -> Option<Vec<DraggableAndCoordinates>> {
+        if name == DEFAULT_LAYER {
+            return None;
+        }
+        let position = self.layers.iter().position(|layer| layer.name == name)?;
+        let id = self.layers.remove(position).id;
+        Some(self.remove_where(|draggable_and_coords| draggable_and_coords.layer == id))
+    }
+    //Indices past the top are treated as the top.
+    fn set_layer_index(&mut self, name: &str, index: usize) -> bool {
+        let position = match self.layers.iter().position(|layer| layer.name == name) {
+            Some(position) => position,
+            None => return false,
+        };
+        let layer = self.layers.remove(position);
+        let index = index.min(self.layers.len());
+        self.layers.insert(index, layer);
+        let layers = &self.layers;
+        //This is a stable sort, so the order within each layer is kept.
         self.draggables_and_locs
-            .retain(|draggable_and_coords| draggable_and_coords.draggable.borrow().retain());
+            .sort_by_key(|draggable_and_coords| {
+                layers
+                    .iter()
+                    .position(|layer| layer.id == draggable_and_coords.layer)
+            });
+        true
+    }
+    fn move_to_layer(&mut self, id: ItemId, layer: &str) -> bool {
+        let index = match self.index_of(id) {
+            Some(index) => index,
+            None => return false,
+        };
+        let mut element = self.draggables_and_locs.remove(index);
+        element.layer = self.layer_id_or_add(layer);
+        let end = self.layer_range(element.layer).end;
+        self.draggables_and_locs.insert(end, element);
+        true
     }
 }
 struct DraggableBorrowAndCoordinates<'a> {
+    id: ItemId,
+    layer: u64,
     draggable: ReferenceBorrow<'a, dyn Draggable>,
     x: f64,
     y: f64,
@@ -104,14 +361,13 @@ struct DraggableBorrowAndCoordinates<'a> {
 struct DraggableSetHolderIterator<'a> {
     holder: &'a DraggableSetHolder,
     index_start: usize,
-    index_back: usize,
+    //Exclusive, so that iterating backward can stop at 0 without going below it.
+    index_end: usize,
 }
 impl<'a> Iterator for DraggableSetHolderIterator<'a> {
     type Item = DraggableBorrowAndCoordinates<'a>;
     fn next(&mut self) -> Option<DraggableBorrowAndCoordinates<'a>> {
-        if self.index_start >= self.holder.draggables_and_locs.len()
-            || self.index_start > self.index_back
-        {
+        if self.index_start >= self.index_end {
             return None;
         }
         let output = self.holder.draggables_and_locs[self.index_start].borrow();
@@ -121,30 +377,147 @@ impl<'a> Iterator for DraggableSetHolderIterator<'a> {
 }
 impl<'a> DoubleEndedIterator for DraggableSetHolderIterator<'a> {
     fn next_back(&mut self) -> Option<DraggableBorrowAndCoordinates<'a>> {
-        //usize type keeps it from going below zero
-        if self.index_back < self.index_start {
+        if self.index_end <= self.index_start {
             return None;
         }
-        let output = self.holder.draggables_and_locs[self.index_back].borrow();
-        self.index_back -= 1;
-        Some(output)
+        self.index_end -= 1;
+        Some(self.holder.draggables_and_locs[self.index_end].borrow())
     }
 }
 struct DragInfo {
+    //Where the drag began in widget coordinates.
+    start_x: f64,
+    start_y: f64,
+    //The object that was pressed. Any other selected objects follow it.
+    id: ItemId,
+    //Every object being dragged and its coordinates when the drag began.
+    items: Vec<(ItemId, f64, f64)>,
+    //The object under the pointer that the pressed object would be dropped onto.
+    drop_target: Cell<Option<ItemId>>,
+}
+//A drag continuing after its objects were thrown.
+struct Throw {
+    drag_info: DragInfo,
+    //In the coordinates objects are placed at per second.
+    velocity_x: f64,
+    velocity_y: f64,
+    //Whether to snap the pressed object to the grid when the objects stop.
+    snap: bool,
+}
+impl DragInfo {
+    fn new(start_x: f64, start_y: f64, id: ItemId, items: Vec<(ItemId, f64, f64)>) -> Self {
+        Self {
+            start_x,
+            start_y,
+            id,
+            items,
+            drop_target: Cell::new(None),
+        }
+    }
+    fn start(&self) -> Option<(f64, f64)> {
+        self.items
+            .iter()
+            .find(|(id, _, _)| *id == self.id)
+            .map(|(_, start_x, start_y)| (*start_x, *start_y))
+    }
+}
+//A press by the pointer or a finger on a touchscreen that has not been released.
+struct Press {
+    //The finger's touch sequence, or None for the pointer.
+    sequence: Option<gdk::EventSequence>,
+    //Where the press began in widget coordinates.
+    start_x: f64,
+    start_y: f64,
+    //The drag of the pressed object and any others following it, or None if the press is panning
+    //the DragArea, drawing a marquee, or did not start a drag.
+    drag_info: Option<DragInfo>,
+}
+//The offset of the pointer or a finger from where it was pressed at a time in microseconds.
+struct DragSample {
+    sequence: Option<gdk::EventSequence>,
+    time: i64,
+    x: f64,
+    y: f64,
+}
+//The last left press and the object it went to, if any.
+#[derive(Clone, Copy)]
+struct LastPress {
+    //In microseconds.
+    time: i64,
+    //In widget coordinates.
+    x: f64,
+    y: f64,
+    target: Option<ItemId>,
+}
+enum ClickType {
+    Single,
+    Double,
+    Middle,
+    Right,
+}
+//A selection rectangle in widget coordinates.
+struct Marquee {
     start_x: f64,
     start_y: f64,
-    index: usize,
-    relative_x: f64,
-    relative_y: f64,
+    width: f64,
+    height: f64,
+    //The selection when the drag began, which is kept when adding to it with Shift or Control.
+    base_selection: Vec<ItemId>,
 }
 
 pub struct DragArea {
     draggables: Rc<RefCell<DraggableSetHolder>>,
-    drag_info: Rc<RefCell<Option<DragInfo>>>,
+    //Every press by the pointer or a finger that has not been released.
+    presses: Rc<RefCell<Vec<Press>>>,
+    selection: Rc<RefCell<Vec<ItemId>>>,
+    focused: Rc<Cell<Option<ItemId>>>,
+    hovered: Rc<Cell<Option<ItemId>>>,
+    //The last position of the pointer in widget coordinates, or None if it is outside the DragArea.
+    pointer: Rc<Cell<Option<(f64, f64)>>>,
+    nudge_step: Rc<Cell<(f64, f64)>>,
+    grid: Rc<Cell<Option<Grid>>>,
+    alignment_guides: Rc<Cell<Option<AlignmentGuides>>>,
+    guides: Rc<RefCell<Vec<guides::Guide>>>,
+    marquee: Rc<RefCell<Option<Marquee>>>,
+    //Where a press with no object under it began, in widget coordinates, for background-clicked.
+    background_press: Rc<Cell<Option<(f64, f64)>>>,
+    //Used to recognize the second press of a double click.
+    last_press: Rc<Cell<Option<LastPress>>>,
     scrollable: Rc<Cell<bool>>,
     scrolling: Rc<Cell<bool>>,
+    raise_on_press: Rc<Cell<bool>>,
+    bounds: Rc<Cell<Option<Rectangle>>>,
+    keep_inside: Rc<Cell<bool>>,
+    shift_axis_lock: Rc<Cell<bool>>,
     translate: Rc<Cell<(f64, f64)>>,
     drag_translate: Rc<Cell<(f64, f64)>>,
+    scroll_limits: Rc<Cell<Option<ScrollLimits>>>,
+    //The adjustments from the Scrollable interface with their value-changed handlers.
+    hadjustment: Rc<RefCell<Option<(Adjustment, glib::SignalHandlerId)>>>,
+    vadjustment: Rc<RefCell<Option<(Adjustment, glib::SignalHandlerId)>>>,
+    hscroll_policy: Rc<Cell<ScrollablePolicy>>,
+    vscroll_policy: Rc<Cell<ScrollablePolicy>>,
+    //Whether the adjustments are being set by the DragArea, so changes to them should be ignored.
+    updating_adjustments: Rc<Cell<bool>>,
+    wheel_scrolling: Rc<Cell<bool>>,
+    wheel_scroll_step: Rc<Cell<f64>>,
+    zoom: Rc<Cell<f64>>,
+    zoom_limits: Rc<Cell<(f64, f64)>>,
+    zoomable: Rc<Cell<bool>>,
+    //The zoom when a pinch began.
+    pinch_zoom: Rc<Cell<f64>>,
+    overscroll: Rc<Cell<bool>>,
+    springing_back: Rc<Cell<bool>>,
+    inertia: Rc<Cell<Option<Inertia>>>,
+    //Recent offsets of the pointer and fingers during drags, used to estimate the velocity when
+    //they end.
+    drag_samples: Rc<RefCell<Vec<DragSample>>>,
+    //The velocity in pixels per second of the DragArea scrolling on after a pan.
+    kinetic_pan: Rc<Cell<Option<(f64, f64)>>>,
+    //Drags continuing after their objects were thrown.
+    throws: Rc<RefCell<Vec<Throw>>>,
+    //Whether the tick callback moving kinetic_pan and throws is running.
+    inertia_running: Rc<Cell<bool>>,
     pre_draw_func: Rc<RefCell<Option<Box<dyn FnMut(&DrawingArea, &Context, i32, i32)>>>>,
     post_draw_func: Rc<RefCell<Option<Box<dyn FnMut(&DrawingArea, &Context, i32, i32)>>>>,
 }
@@ -153,35 +526,2030 @@ impl DragArea {
         let draggables = Rc::new(RefCell::new(DraggableSetHolder::new()));
         Self {
             draggables: draggables,
-            drag_info: Rc::new(RefCell::new(None)),
+            presses: Rc::new(RefCell::new(Vec::new())),
+            selection: Rc::new(RefCell::new(Vec::new())),
+            focused: Rc::new(Cell::new(None)),
+            hovered: Rc::new(Cell::new(None)),
+            pointer: Rc::new(Cell::new(None)),
+            nudge_step: Rc::new(Cell::new((1.0, 10.0))),
+            grid: Rc::new(Cell::new(None)),
+            alignment_guides: Rc::new(Cell::new(None)),
+            guides: Rc::new(RefCell::new(Vec::new())),
+            marquee: Rc::new(RefCell::new(None)),
+            background_press: Rc::new(Cell::new(None)),
+            last_press: Rc::new(Cell::new(None)),
             scrollable: Rc::new(Cell::new(false)),
             scrolling: Rc::new(Cell::new(false)),
+            raise_on_press: Rc::new(Cell::new(true)),
+            bounds: Rc::new(Cell::new(None)),
+            keep_inside: Rc::new(Cell::new(false)),
+            shift_axis_lock: Rc::new(Cell::new(true)),
             translate: Rc::new(Cell::new((0.0, 0.0))),
             drag_translate: Rc::new(Cell::new((0.0, 0.0))),
+            scroll_limits: Rc::new(Cell::new(None)),
+            hadjustment: Rc::new(RefCell::new(None)),
+            vadjustment: Rc::new(RefCell::new(None)),
+            hscroll_policy: Rc::new(Cell::new(ScrollablePolicy::Minimum)),
+            vscroll_policy: Rc::new(Cell::new(ScrollablePolicy::Minimum)),
+            updating_adjustments: Rc::new(Cell::new(false)),
+            wheel_scrolling: Rc::new(Cell::new(true)),
+            wheel_scroll_step: Rc::new(Cell::new(50.0)),
+            zoom: Rc::new(Cell::new(1.0)),
+            zoom_limits: Rc::new(Cell::new((0.1, 10.0))),
+            zoomable: Rc::new(Cell::new(false)),
+            pinch_zoom: Rc::new(Cell::new(1.0)),
+            overscroll: Rc::new(Cell::new(true)),
+            springing_back: Rc::new(Cell::new(false)),
+            inertia: Rc::new(Cell::new(None)),
+            drag_samples: Rc::new(RefCell::new(Vec::new())),
+            kinetic_pan: Rc::new(Cell::new(None)),
+            throws: Rc::new(RefCell::new(Vec::new())),
+            inertia_running: Rc::new(Cell::new(false)),
             pre_draw_func: Rc::new(RefCell::new(None)),
             post_draw_func: Rc::new(RefCell::new(None)),
         }
     }
-    pub fn push_box(&self, item: Box<impl Draggable + 'static>, x: f64, y: f64) {
-        self.draggables
+    pub fn push_box(
+        &self,
+        layer: &str,
+        item: Box<impl Draggable + 'static>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
+        let id =
+            self.draggables
+                .borrow_mut()
+                .push(layer, (item as Box<dyn Draggable>).into(), x, y);
+        self.update_adjustments();
+        id
+    }
+    pub fn push_rc(
+        &self,
+        layer: &str,
+        item: Rc<impl Draggable + 'static>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
+        let id = self
+            .draggables
+            .borrow_mut()
+            .push(layer, (item as Rc<dyn Draggable>).into(), x, y);
+        self.update_adjustments();
+        id
+    }
+    pub fn push_rc_ref_cell(
+        &self,
+        layer: &str,
+        item: Rc<RefCell<impl Draggable + 'static>>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
+        let id = self.draggables.borrow_mut().push(
+            layer,
+            (item as Rc<RefCell<dyn Draggable>>).into(),
+            x,
+            y,
+        );
+        self.update_adjustments();
+        id
+    }
+    pub fn remove(&self, id: ItemId) -> bool {
+        //Bind this first so that the holder is no longer borrowed when on_remove is called.
+        let removed = self.draggables.borrow_mut().remove(id);
+        match removed {
+            Some(draggable_and_coords) => {
+                self.notify_removed(vec![draggable_and_coords]);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn remove_where(&self, mut predicate: impl FnMut(ItemId, &dyn Draggable) -> bool) {
+        //The predicate is run without the DraggableSetHolder borrowed so that it can use the
+        //DragArea.
+        let draggables: Vec<(ItemId, SharedDraggable)> = self
+            .draggables
+            .borrow()
+            .draggables_and_locs
+            .iter()
+            .map(|draggable_and_coords| {
+                (
+                    draggable_and_coords.id,
+                    draggable_and_coords.draggable.clone(),
+                )
+            })
+            .collect();
+        let ids: Vec<ItemId> = draggables
+            .into_iter()
+            .filter(|(id, draggable)| predicate(*id, &*draggable.borrow()))
+            .map(|(id, _)| id)
+            .collect();
+        let removed = self
+            .draggables
             .borrow_mut()
-            .push((item as Box<dyn Draggable>).into(), x, y);
+            .remove_where(|draggable_and_coords| ids.contains(&draggable_and_coords.id));
+        self.notify_removed(removed);
+    }
+    pub fn clear(&self) {
+        let removed = self.draggables.borrow_mut().remove_where(|_| true);
+        self.notify_removed(removed);
+    }
+    fn notify_removed(&self, removed: Vec<DraggableAndCoordinates>) {
+        let old_selection = self.selection();
+        self.selection.borrow_mut().retain(|selected| {
+            !removed
+                .iter()
+                .any(|draggable_and_coords| draggable_and_coords.id == *selected)
+        });
+        if let Some(focused) = self.focused.get() {
+            if removed
+                .iter()
+                .any(|draggable_and_coords| draggable_and_coords.id == focused)
+            {
+                self.focused.set(None);
+            }
+        }
+        if let Some(hovered) = self.hovered.get() {
+            if removed
+                .iter()
+                .any(|draggable_and_coords| draggable_and_coords.id == hovered)
+            {
+                self.hovered.set(None);
+            }
+        }
+        self.emit_selection_changed(&old_selection);
+        //This is run on every draw, so only update the adjustments if something was removed.
+        if !removed.is_empty() {
+            self.update_adjustments();
+        }
+        for draggable_and_coords in removed {
+            draggable_and_coords.draggable.borrow().on_remove();
+            self.emit_item_signal(
+                "item-removed",
+                draggable_and_coords.id,
+                draggable_and_coords.x,
+                draggable_and_coords.y,
+            );
+        }
+    }
+    fn emit_item_signal(&self, name: &str, id: ItemId, x: f64, y: f64) {
+        self.obj().emit_by_name::<()>(name, &[&id.0, &x, &y]);
+    }
+    //Emits selection-changed if the selection differs from the given old one.
+    fn emit_selection_changed(&self, old_selection: &[ItemId]) {
+        if *self.selection.borrow() != old_selection {
+            self.obj().emit_by_name::<()>("selection-changed", &[]);
+        }
+    }
+    fn emit_scroll_changed(&self) {
+        self.update_adjustments();
+        let (x, y) = self.get_scroll_location();
+        self.obj().emit_by_name::<()>("scroll-changed", &[&x, &y]);
+    }
+    //Finds the topmost object under the pointer, given in widget coordinates or as None when the
+    //pointer has left the DragArea, and runs the pointer callbacks.
+    fn update_hover(&self, pointer: Option<(f64, f64)>) {
+        self.pointer.set(pointer);
+        let hovered = pointer.and_then(|(x, y)| {
+            let (x, y) = self.to_scene(x, y, Coordinates::Widget);
+            let draggables = self.draggables.borrow();
+            let hovered = draggables.hits(x, y).first().map(|draggable_and_coords| {
+                (
+                    draggable_and_coords.id,
+                    x - draggable_and_coords.x,
+                    y - draggable_and_coords.y,
+                )
+            });
+            hovered
+        });
+        let old = self.hovered.replace(hovered.map(|(id, _, _)| id));
+        if old != self.hovered.get() {
+            self.for_each_draggable(old, |_, draggable| draggable.on_pointer_leave());
+            self.for_each_draggable(self.hovered.get(), |_, draggable| {
+                draggable.on_pointer_enter()
+            });
+            self.obj().queue_draw();
+        }
+        if let Some((id, x, y)) = hovered {
+            self.for_each_draggable([id], |_, draggable| draggable.on_pointer_motion(x, y));
+        }
+        self.update_cursor();
+    }
+    //Shows the cursor of the hovered object, "all-scroll" where the DragArea can be scrolled, or
+    //the default cursor.
+    fn update_cursor(&self) {
+        if !self.presses.borrow().is_empty() {
+            return;
+        }
+        let pointer = self
+            .pointer
+            .get()
+            .map(|(x, y)| self.to_scene(x, y, Coordinates::Widget));
+        let cursor = match (pointer, self.hovered.get()) {
+            (Some((x, y)), Some(id)) => {
+                let draggable = self
+                    .draggables
+                    .borrow()
+                    .get(id)
+                    .map(|draggable_and_coords| {
+                        (
+                            draggable_and_coords.draggable.clone(),
+                            x - draggable_and_coords.x,
+                            y - draggable_and_coords.y,
+                        )
+                    });
+                match draggable {
+                    Some((draggable, x, y)) => draggable
+                        .borrow()
+                        .cursor(x, y)
+                        .or_else(|| gdk::Cursor::from_name("grab", None)),
+                    None => None,
+                }
+            }
+            (Some((x, y)), None) if self.scrollable.get() && self.can_scroll_at(x, y) => {
+                gdk::Cursor::from_name("all-scroll", None)
+            }
+            _ => None,
+        };
+        self.obj().set_cursor(cursor.as_ref());
+    }
+    //Whether dragging from the given point in scene coordinates would scroll the DragArea if it is
+    //scrollable.
+    fn can_scroll_at(&self, x: f64, y: f64) -> bool {
+        self.draggables
+            .borrow()
+            .iter_interactive()
+            .all(|draggable_and_coords| {
+                draggable_and_coords
+                    .draggable
+                    .can_scroll(x - draggable_and_coords.x, y - draggable_and_coords.y)
+            })
+    }
+    pub fn position(&self, id: ItemId) -> Option<(f64, f64)> {
+        self.draggables
+            .borrow()
+            .get(id)
+            .map(|draggable_and_coords| (draggable_and_coords.x, draggable_and_coords.y))
+    }
+    pub fn set_position(&self, id: ItemId, x: f64, y: f64) -> bool {
+        {
+            let mut draggables = self.draggables.borrow_mut();
+            let draggable_and_coords = match draggables.get_mut(id) {
+                Some(draggable_and_coords) => draggable_and_coords,
+                None => return false,
+            };
+            let limits = draggable_and_coords.draggable.borrow().get_limits();
+            (draggable_and_coords.x, draggable_and_coords.y) = self.clamp_position(limits, x, y);
+        }
+        self.update_adjustments();
+        true
+    }
+    pub fn move_by(&self, id: ItemId, dx: f64, dy: f64) -> bool {
+        match self.position(id) {
+            Some((x, y)) => self.set_position(id, x + dx, y + dy),
+            None => false,
+        }
+    }
+    pub fn set_constraint(&self, id: ItemId, constraint: Option<Constraint>) -> bool {
+        match self.draggables.borrow_mut().get_mut(id) {
+            Some(draggable_and_coords) => {
+                draggable_and_coords.constraint = constraint;
+                true
+            }
+            None => false,
+        }
     }
-    pub fn push_rc(&self, item: Rc<impl Draggable + 'static>, x: f64, y: f64) {
+    pub fn constraint(&self, id: ItemId) -> Option<Constraint> {
         self.draggables
+            .borrow()
+            .get(id)
+            .and_then(|draggable_and_coords| draggable_and_coords.constraint)
+    }
+    pub fn set_shift_axis_lock(&self, shift_axis_lock: bool) {
+        self.shift_axis_lock.set(shift_axis_lock);
+    }
+    pub fn z_index(&self, id: ItemId) -> Option<usize> {
+        self.draggables.borrow().z_index(id)
+    }
+    pub fn set_z_index(&self, id: ItemId, z_index: usize) -> bool {
+        self.draggables.borrow_mut().set_z_index(id, z_index)
+    }
+    pub fn raise(&self, id: ItemId) -> bool {
+        match self.z_index(id) {
+            Some(z_index) => self.set_z_index(id, z_index + 1),
+            None => false,
+        }
+    }
+    pub fn lower(&self, id: ItemId) -> bool {
+        match self.z_index(id) {
+            Some(z_index) => self.set_z_index(id, z_index.saturating_sub(1)),
+            None => false,
+        }
+    }
+    pub fn bring_to_front(&self, id: ItemId) -> bool {
+        self.set_z_index(id, usize::MAX)
+    }
+    pub fn send_to_back(&self, id: ItemId) -> bool {
+        self.set_z_index(id, 0)
+    }
+    pub fn set_raise_on_press(&self, raise_on_press: bool) {
+        self.raise_on_press.set(raise_on_press);
+    }
+    pub fn selection(&self) -> Vec<ItemId> {
+        self.selection.borrow().clone()
+    }
+    pub fn is_selected(&self, id: ItemId) -> bool {
+        self.selection.borrow().contains(&id)
+    }
+    pub fn select(&self, id: ItemId) -> bool {
+        if self.draggables.borrow().get(id).is_none() {
+            return false;
+        }
+        let old_selection = self.selection();
+        if !old_selection.contains(&id) {
+            self.selection.borrow_mut().push(id);
+        }
+        self.emit_selection_changed(&old_selection);
+        true
+    }
+    pub fn deselect(&self, id: ItemId) -> bool {
+        let old_selection = self.selection();
+        self.selection
             .borrow_mut()
-            .push((item as Rc<dyn Draggable>).into(), x, y);
+            .retain(|selected| *selected != id);
+        self.emit_selection_changed(&old_selection);
+        old_selection.contains(&id)
+    }
+    pub fn set_selection(&self, ids: impl IntoIterator<Item = ItemId>) {
+        let old_selection = self.selection();
+        let mut selection = Vec::new();
+        {
+            let draggables = self.draggables.borrow();
+            for id in ids {
+                if draggables.get(id).is_some() && !selection.contains(&id) {
+                    selection.push(id);
+                }
+            }
+        }
+        *self.selection.borrow_mut() = selection;
+        self.emit_selection_changed(&old_selection);
+    }
+    pub fn clear_selection(&self) {
+        let old_selection = self.selection.take();
+        self.emit_selection_changed(&old_selection);
+    }
+    pub fn add_layer(&self, name: &str) -> bool {
+        self.draggables.borrow_mut().add_layer(name)
+    }
+    pub fn remove_layer(&self, name: &str) -> bool {
+        let removed = self.draggables.borrow_mut().remove_layer(name);
+        match removed {
+            Some(removed) => {
+                self.notify_removed(removed);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn layers(&self) -> Vec<String> {
+        self.draggables
+            .borrow()
+            .layers
+            .iter()
+            .map(|layer| layer.name.clone())
+            .collect()
+    }
+    pub fn set_layer_visible(&self, name: &str, visible: bool) -> bool {
+        let found = match self.draggables.borrow_mut().layer_mut(name) {
+            Some(layer) => {
+                layer.visible = visible;
+                true
+            }
+            None => false,
+        };
+        self.update_adjustments();
+        found
+    }
+    pub fn set_layer_locked(&self, name: &str, locked: bool) -> bool {
+        match self.draggables.borrow_mut().layer_mut(name) {
+            Some(layer) => {
+                layer.locked = locked;
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn set_layer_index(&self, name: &str, index: usize) -> bool {
+        self.draggables.borrow_mut().set_layer_index(name, index)
+    }
+    pub fn layer_of(&self, id: ItemId) -> Option<String> {
+        let draggables = self.draggables.borrow();
+        let layer = draggables.get(id)?.layer;
+        Some(draggables.layer_by_id(layer).name.clone())
+    }
+    pub fn move_to_layer(&self, id: ItemId, layer: &str) -> bool {
+        let found = self.draggables.borrow_mut().move_to_layer(id, layer);
+        self.update_adjustments();
+        found
+    }
+    pub fn set_bounds(&self, bounds: Option<Rectangle>) {
+        self.bounds.set(bounds);
+        if self.keep_inside.get() {
+            self.push_inside();
+        }
+    }
+    pub fn bounds(&self) -> Option<Rectangle> {
+        self.bounds.get()
+    }
+    pub fn set_keep_inside(&self, keep_inside: bool) {
+        self.keep_inside.set(keep_inside);
+        if keep_inside {
+            self.push_inside();
+        }
     }
-    pub fn push_rc_ref_cell(&self, item: Rc<RefCell<impl Draggable + 'static>>, x: f64, y: f64) {
+    //The rectangle in scene coordinates that objects are kept inside, if any. This is the explicit
+    //bounds if they are set and otherwise the allocated size of a non-scrollable DragArea.
+    fn current_bounds(&self) -> Option<Rectangle> {
+        if let Some(bounds) = self.bounds.get() {
+            return Some(bounds);
+        }
+        if self.scrollable.get() {
+            return None;
+        }
+        let obj = self.obj();
+        //The DragArea has no size until it is first allocated, so use the requested size until
+        //then.
+        let (width, height) = if obj.width() > 0 && obj.height() > 0 {
+            (obj.width(), obj.height())
+        } else {
+            (obj.width_request(), obj.height_request())
+        };
+        let zoom = self.zoom.get();
+        Some(Rectangle::new(
+            0.0,
+            0.0,
+            width as f64 / zoom,
+            height as f64 / zoom,
+        ))
+    }
+    fn clamp_position(&self, limits: (f64, f64, f64, f64), x: f64, y: f64) -> (f64, f64) {
+        let (neg_x_limit, pos_x_limit, neg_y_limit, pos_y_limit) = limits;
+        let bounds = match self.current_bounds() {
+            Some(bounds) => bounds,
+            None => return (x, y),
+        };
+        (
+            calculate_limits(
+                neg_x_limit,
+                pos_x_limit,
+                bounds.x.min(bounds.x + bounds.width),
+                bounds.x.max(bounds.x + bounds.width),
+                x,
+            ),
+            calculate_limits(
+                neg_y_limit,
+                pos_y_limit,
+                bounds.y.min(bounds.y + bounds.height),
+                bounds.y.max(bounds.y + bounds.height),
+                y,
+            ),
+        )
+    }
+    //Moves every object that is not inside the bounds back inside.
+    fn push_inside(&self) {
+        let mut moved = Vec::new();
+        {
+            let mut draggables = self.draggables.borrow_mut();
+            for draggable_and_coords in draggables.draggables_and_locs.iter_mut() {
+                let limits = draggable_and_coords.draggable.borrow().get_limits();
+                let (x, y) =
+                    self.clamp_position(limits, draggable_and_coords.x, draggable_and_coords.y);
+                if (x, y) != (draggable_and_coords.x, draggable_and_coords.y) {
+                    moved.push((
+                        draggable_and_coords.id,
+                        draggable_and_coords.x,
+                        draggable_and_coords.y,
+                    ));
+                    draggable_and_coords.x = x;
+                    draggable_and_coords.y = y;
+                }
+            }
+        }
+        self.emit_item_moved(&moved);
+        self.obj().queue_draw();
+    }
+    pub fn items_at(&self, x: f64, y: f64, coordinates: Coordinates) -> Vec<ItemId> {
+        let (x, y) = self.to_scene(x, y, coordinates);
         self.draggables
+            .borrow()
+            .iter_visible()
+            .rev()
+            .filter(|draggable_and_coords| {
+                draggable_and_coords
+                    .draggable
+                    .contains(x - draggable_and_coords.x, y - draggable_and_coords.y)
+            })
+            .map(|draggable_and_coords| draggable_and_coords.id)
+            .collect()
+    }
+    pub fn items_in_rect(&self, rect: Rectangle, coordinates: Coordinates) -> Vec<ItemId> {
+        let (left, top, right, bottom) = self.scene_rect(rect, coordinates);
+        self.draggables.borrow().in_rect(left, top, right, bottom)
+    }
+    //The left, top, right, and bottom of a rectangle in scene coordinates. This allows rectangles
+    //with negative sizes, such as one dragged up and to the left.
+    fn scene_rect(&self, rect: Rectangle, coordinates: Coordinates) -> (f64, f64, f64, f64) {
+        let (x0, y0) = self.to_scene(rect.x, rect.y, coordinates);
+        let (x1, y1) = self.to_scene(rect.x + rect.width, rect.y + rect.height, coordinates);
+        (x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1))
+    }
+    fn to_scene(&self, x: f64, y: f64, coordinates: Coordinates) -> (f64, f64) {
+        match coordinates {
+            Coordinates::Widget => {
+                let (scroll_x, scroll_y) = self.get_scroll_location();
+                let zoom = self.zoom.get();
+                ((x - scroll_x) / zoom, (y - scroll_y) / zoom)
+            }
+            Coordinates::Scene => (x, y),
+        }
+    }
+    fn to_widget(&self, x: f64, y: f64) -> (f64, f64) {
+        let (scroll_x, scroll_y) = self.get_scroll_location();
+        let zoom = self.zoom.get();
+        (x * zoom + scroll_x, y * zoom + scroll_y)
+    }
+    pub fn focused(&self) -> Option<ItemId> {
+        self.focused.get()
+    }
+    pub fn set_focused(&self, id: Option<ItemId>) -> bool {
+        if let Some(id) = id {
+            if self.draggables.borrow().get(id).is_none() {
+                return false;
+            }
+        }
+        self.focused.set(id);
+        true
+    }
+    pub fn set_nudge_step(&self, step: f64, large_step: f64) {
+        self.nudge_step.set((step, large_step));
+    }
+    pub fn set_grid(&self, grid: Option<Grid>) {
+        self.grid.set(grid);
+    }
+    pub fn grid(&self) -> Option<Grid> {
+        self.grid.get()
+    }
+    pub fn set_alignment_guides(&self, alignment_guides: Option<AlignmentGuides>) {
+        self.alignment_guides.set(alignment_guides);
+    }
+    pub fn alignment_guides(&self) -> Option<AlignmentGuides> {
+        self.alignment_guides.get()
+    }
+    //The selected objects, or the focused object if none are selected, skipping any that cannot
+    //be dragged or clicked.
+    fn keyboard_targets(&self) -> Vec<ItemId> {
+        let draggables = self.draggables.borrow();
+        let selection = self.selection.borrow();
+        let targets = if selection.is_empty() {
+            self.focused.get().into_iter().collect()
+        } else {
+            selection.clone()
+        };
+        targets
+            .into_iter()
+            .filter(|id| match draggables.get(*id) {
+                Some(draggable_and_coords) => draggables.is_interactive(draggable_and_coords.layer),
+                None => false,
+            })
+            .collect()
+    }
+    fn key_pressed(&self, key: gdk::Key, modifiers: gdk::ModifierType) -> glib::Propagation {
+        let shift = modifiers.contains(gdk::ModifierType::SHIFT_MASK);
+        let (step, large_step) = self.nudge_step.get();
+        let step = if shift { large_step } else { step };
+        let (dx, dy) = match key {
+            gdk::Key::Left | gdk::Key::KP_Left => (-step, 0.0),
+            gdk::Key::Right | gdk::Key::KP_Right => (step, 0.0),
+            gdk::Key::Up | gdk::Key::KP_Up => (0.0, -step),
+            gdk::Key::Down | gdk::Key::KP_Down => (0.0, step),
+            gdk::Key::Tab | gdk::Key::KP_Tab | gdk::Key::ISO_Left_Tab => {
+                return self.cycle_focus(shift || key == gdk::Key::ISO_Left_Tab);
+            }
+            gdk::Key::Delete | gdk::Key::KP_Delete | gdk::Key::BackSpace => {
+                let targets = self.keyboard_targets();
+                if targets.is_empty() {
+                    return glib::Propagation::Proceed;
+                }
+                for id in targets {
+                    self.remove(id);
+                }
+                self.obj().queue_draw();
+                return glib::Propagation::Stop;
+            }
+            gdk::Key::Return | gdk::Key::KP_Enter | gdk::Key::ISO_Enter => {
+                let targets = self.keyboard_targets();
+                if targets.is_empty() {
+                    return glib::Propagation::Proceed;
+                }
+                self.for_each_draggable(targets.iter().copied(), |_, draggable| {
+                    draggable.on_activate()
+                });
+                for id in targets {
+                    if let Some((x, y)) = self.position(id) {
+                        self.emit_item_signal("item-activated", id, x, y);
+                    }
+                }
+                self.obj().queue_draw();
+                return glib::Propagation::Stop;
+            }
+            _ => return glib::Propagation::Proceed,
+        };
+        let targets = self.keyboard_targets();
+        if targets.is_empty() {
+            return glib::Propagation::Proceed;
+        }
+        let items: Vec<_> = targets
+            .into_iter()
+            .filter_map(|id| self.position(id).map(|(x, y)| (id, x, y)))
+            .collect();
+        self.move_group(&items, dx, dy);
+        self.emit_item_moved(&items);
+        self.obj().queue_draw();
+        glib::Propagation::Stop
+    }
+    //Moves focus to the next or previous object in stacking order, selecting it. When there is no
+    //such object, focus is cleared and the key is left for GTK to move focus out of the DragArea.
+    fn cycle_focus(&self, backward: bool) -> glib::Propagation {
+        let ids: Vec<ItemId> = self
+            .draggables
+            .borrow()
+            .iter_interactive()
+            .map(|draggable_and_coords| draggable_and_coords.id)
+            .collect();
+        let next = match self
+            .focused
+            .get()
+            .and_then(|focused| ids.iter().position(|id| *id == focused))
+        {
+            Some(index) if backward => index.checked_sub(1).map(|index| ids[index]),
+            Some(index) => ids.get(index + 1).copied(),
+            None if backward => ids.last().copied(),
+            None => ids.first().copied(),
+        };
+        self.focused.set(next);
+        let old_selection = self.selection.replace(next.into_iter().collect());
+        self.emit_selection_changed(&old_selection);
+        self.obj().queue_draw();
+        match next {
+            Some(_) => glib::Propagation::Stop,
+            None => glib::Propagation::Proceed,
+        }
+    }
+    fn draw_focus(&self, context: &Context) -> Result<(), Error> {
+        if !self.obj().has_focus() {
+            return Ok(());
+        }
+        let focused = match self.focused.get() {
+            Some(focused) => focused,
+            None => return Ok(()),
+        };
+        let draggables = self.draggables.borrow();
+        let draggable_and_coords = match draggables.get(focused) {
+            Some(draggable_and_coords) => draggable_and_coords,
+            None => return Ok(()),
+        };
+        let (neg_x, pos_x, neg_y, pos_y) = draggable_and_coords.draggable.borrow().get_limits();
+        let (left, top) = self.to_widget(
+            draggable_and_coords.x - neg_x,
+            draggable_and_coords.y - neg_y,
+        );
+        let (right, bottom) = self.to_widget(
+            draggable_and_coords.x + pos_x,
+            draggable_and_coords.y + pos_y,
+        );
+        context.rectangle(
+            left - 2.0,
+            top - 2.0,
+            right - left + 4.0,
+            bottom - top + 4.0,
+        );
+        context.set_source_rgba(0.0, 0.0, 0.0, 0.6);
+        context.set_line_width(1.0);
+        context.set_dash(&[4.0, 2.0], 0.0);
+        context.stroke()?;
+        context.set_dash(&[], 0.0);
+        Ok(())
+    }
+    //Begins a press by the pointer, whose sequence is None, or by a finger. While other fingers
+    //are dragging objects, a finger can only drag the object under it, and nothing else can be
+    //pressed while the DragArea is being panned or a marquee is drawn.
+    fn drag_begin(
+        &self,
+        sequence: Option<gdk::EventSequence>,
+        x: f64,
+        y: f64,
+        modifiers: gdk::ModifierType,
+        device: Option<gdk::InputSource>,
+    ) {
+        //Catch the DragArea where it is if it is springing back or moving on after a pan, and
+        //catch thrown objects. This is done before finding the pressed object since finishing the
+        //drags of thrown objects runs callbacks that may move or remove objects.
+        self.springing_back.set(false);
+        self.kinetic_pan.set(None);
+        for throw in self.throws.take() {
+            self.finish_throw(throw);
+        }
+        let (scene_x, scene_y) = self.to_scene(x, y, Coordinates::Widget);
+        let pressed = self.press_target(x, y, modifiers, device);
+        let multiple = !self.presses.borrow().is_empty();
+        if multiple {
+            let presses = self.presses.borrow();
+            let blocked = presses.iter().any(|press| match &press.drag_info {
+                Some(drag_info) => {
+                    press.sequence == sequence
+                        || pressed.is_none()
+                        || drag_info
+                            .items
+                            .iter()
+                            .any(|(id, _, _)| Some(*id) == pressed)
+                }
+                None => true,
+            });
+            if blocked {
+                return;
+            }
+        }
+        self.obj().grab_focus();
+        //Focus handlers may have removed the pressed object.
+        if let Some(id) = pressed {
+            if self.draggables.borrow().get(id).is_none() {
+                return;
+            }
+        }
+        self.drag_samples
+            .borrow_mut()
+            .retain(|drag_sample| drag_sample.sequence != sequence);
+        let scrolling = self.can_scroll_at(scene_x, scene_y);
+        if !multiple {
+            self.scrolling.set(scrolling);
+        }
+        let toggle =
+            modifiers.intersects(gdk::ModifierType::SHIFT_MASK | gdk::ModifierType::CONTROL_MASK);
+        let old_selection = self.selection();
+        let mut new_drag_info = None;
+        match pressed {
+            Some(id) => {
+                self.focused.set(Some(id));
+                if self.raise_on_press.get() {
+                    let mut draggables = self.draggables.borrow_mut();
+                    if let Some(index) = draggables.index_of(id) {
+                        draggables.move_to_end(index);
+                    }
+                }
+                let mut selection = self.selection.borrow_mut();
+                if multiple {
+                    //Only the pressed object follows another finger.
+                    if !selection.contains(&id) {
+                        selection.push(id);
+                    }
+                    new_drag_info = self
+                        .draggables
+                        .borrow()
+                        .get(id)
+                        .map(|draggable_and_coords| {
+                            DragInfo::new(
+                                x,
+                                y,
+                                id,
+                                vec![(id, draggable_and_coords.x, draggable_and_coords.y)],
+                            )
+                        });
+                } else if toggle && selection.contains(&id) {
+                    //Deselecting an object does not start dragging it.
+                    selection.retain(|selected| *selected != id);
+                } else {
+                    if !selection.contains(&id) {
+                        if !toggle {
+                            selection.clear();
+                        }
+                        selection.push(id);
+                    }
+                    let draggables = self.draggables.borrow();
+                    let items = selection
+                        .iter()
+                        .filter_map(|selected| draggables.get(*selected))
+                        .filter(|draggable_and_coords| {
+                            draggables.is_interactive(draggable_and_coords.layer)
+                        })
+                        .map(|draggable_and_coords| {
+                            (
+                                draggable_and_coords.id,
+                                draggable_and_coords.x,
+                                draggable_and_coords.y,
+                            )
+                        })
+                        .collect();
+                    new_drag_info = Some(DragInfo::new(x, y, id, items));
+                }
+            }
+            None => {
+                self.background_press.set(Some((x, y)));
+                if !(self.scrollable.get() && scrolling) {
+                    if !toggle {
+                        self.selection.borrow_mut().clear();
+                    }
+                    *self.marquee.borrow_mut() = Some(Marquee {
+                        start_x: x,
+                        start_y: y,
+                        width: 0.0,
+                        height: 0.0,
+                        base_selection: self.selection(),
+                    });
+                }
+            }
+        }
+        let dragged: Vec<ItemId> = match new_drag_info.as_ref() {
+            Some(drag_info) => drag_info.items.iter().map(|(id, _, _)| *id).collect(),
+            None => Vec::new(),
+        };
+        let cursor = if multiple {
+            None
+        } else if new_drag_info.is_some() {
+            Some("grabbing")
+        } else if pressed.is_none() && self.scrollable.get() && scrolling {
+            Some("all-scroll")
+        } else {
+            None
+        };
+        if let Some(cursor) = cursor {
+            self.obj().set_cursor_from_name(Some(cursor));
+        }
+        self.presses.borrow_mut().push(Press {
+            sequence,
+            start_x: x,
+            start_y: y,
+            drag_info: new_drag_info,
+        });
+        self.emit_selection_changed(&old_selection);
+        self.for_each_draggable(dragged.iter().copied(), |_, draggable| {
+            draggable.on_drag_begin()
+        });
+        for id in dragged {
+            if let Some((x, y)) = self.position(id) {
+                self.emit_item_signal("item-drag-begin", id, x, y);
+            }
+        }
+        self.obj().queue_draw();
+    }
+    //Runs on_click on the objects under a left press, topmost first, and returns the one that
+    //returned Propagation::Stop, which is the object pressed, or None if the press goes to the
+    //background. The second press of a double click goes to the same object as the first without
+    //running on_click since the GestureClick runs on_double_click for it.
+    fn press_target(
+        &self,
+        x: f64,
+        y: f64,
+        modifiers: gdk::ModifierType,
+        device: Option<gdk::InputSource>,
+    ) -> Option<ItemId> {
+        let time = glib::monotonic_time();
+        let settings = self.obj().settings();
+        let double_click_time = settings.gtk_double_click_time() as i64 * 1000;
+        let double_click_distance = settings.gtk_double_click_distance() as f64;
+        if let Some(last_press) = self.last_press.take() {
+            if time - last_press.time <= double_click_time
+                && (x - last_press.x).hypot(y - last_press.y) <= double_click_distance
+            {
+                return last_press
+                    .target
+                    .filter(|id| self.draggables.borrow().get(*id).is_some());
+            }
+        }
+        let event = PointerEvent {
+            x: 0.0,
+            y: 0.0,
+            widget_x: x,
+            widget_y: y,
+            button: 1,
+            click_count: 1,
+            modifiers,
+            device,
+        };
+        let target = self.click(ClickType::Single, event);
+        self.last_press.set(Some(LastPress { time, x, y, target }));
+        target
+    }
+    //Continues a press given the offset from where it began.
+    fn drag_update(
+        &self,
+        sequence: Option<gdk::EventSequence>,
+        x: f64,
+        y: f64,
+        modifiers: gdk::ModifierType,
+    ) {
+        let dragged: Vec<ItemId> = {
+            let presses = self.presses.borrow();
+            let press = match presses.iter().find(|press| press.sequence == sequence) {
+                Some(press) => press,
+                None => return,
+            };
+            self.record_drag_sample(sequence, x, y);
+            let drag_info = match press.drag_info.as_ref() {
+                Some(drag_info) => drag_info,
+                None => {
+                    if self.update_marquee(x, y) {
+                        self.obj().queue_draw();
+                    } else if self.scrollable.get() && self.scrolling.get() {
+                        let (trans_x, trans_y) = self.translate.get();
+                        let (new_x, new_y) = self.overscroll_location(trans_x + x, trans_y + y);
+                        self.drag_translate.set((new_x - trans_x, new_y - trans_y));
+                        self.emit_scroll_changed();
+                        self.obj().queue_draw();
+                    }
+                    return;
+                }
+            };
+            self.move_dragged(drag_info, x, y, modifiers, false);
+            self.update_drop_target(drag_info, x, y);
+            drag_info.items.iter().map(|(id, _, _)| *id).collect()
+        };
+        self.for_each_draggable(dragged, |id, draggable| {
+            if let Some((x, y)) = self.position(id) {
+                draggable.on_drag_motion(x, y);
+            }
+        });
+        self.obj().queue_draw();
+    }
+    //The topmost object under the pointer, given as an offset from where the drag began, that is
+    //not being dragged and accepts the pressed object being dropped onto it.
+    fn find_drop_target(&self, drag_info: &DragInfo, x: f64, y: f64) -> Option<ItemId> {
+        let (pointer_x, pointer_y) = self.to_scene(
+            drag_info.start_x + x,
+            drag_info.start_y + y,
+            Coordinates::Widget,
+        );
+        //Collect the candidates first so that contains and accepts_drop can use the DragArea.
+        let (dragged, candidates) = {
+            let draggables = self.draggables.borrow();
+            let dragged = draggables.get_draggable(drag_info.id)?;
+            let candidates: Vec<(ItemId, SharedDraggable, f64, f64)> = draggables
+                .draggables_and_locs
+                .iter()
+                .rev()
+                .filter(|draggable_and_coords| {
+                    draggables.layer_by_id(draggable_and_coords.layer).visible
+                        && !drag_info
+                            .items
+                            .iter()
+                            .any(|(id, _, _)| *id == draggable_and_coords.id)
+                })
+                .map(|draggable_and_coords| {
+                    (
+                        draggable_and_coords.id,
+                        draggable_and_coords.draggable.clone(),
+                        draggable_and_coords.x,
+                        draggable_and_coords.y,
+                    )
+                })
+                .collect();
+            (dragged, candidates)
+        };
+        let dragged = dragged.borrow();
+        candidates
+            .into_iter()
+            .find(|(_, draggable, x, y)| {
+                let draggable = draggable.borrow();
+                draggable.contains(pointer_x - x, pointer_y - y)
+                    && draggable.accepts_drop(&*dragged)
+            })
+            .map(|(id, _, _, _)| id)
+    }
+    fn update_drop_target(&self, drag_info: &DragInfo, x: f64, y: f64) {
+        let new_target = self.find_drop_target(drag_info, x, y);
+        if let Some((dragged, target)) = self.set_drop_target(drag_info, new_target) {
+            target.borrow().on_drag_over(&*dragged.borrow());
+        }
+    }
+    //Returns whether the pressed object was dropped onto another.
+    fn drop_dragged(&self, drag_info: &DragInfo, x: f64, y: f64) -> bool {
+        let new_target = self.find_drop_target(drag_info, x, y);
+        let dragged_and_target = self.set_drop_target(drag_info, new_target);
+        drag_info.drop_target.set(None);
+        match dragged_and_target {
+            Some((dragged, target)) => {
+                target.borrow().on_drop(&*dragged.borrow());
+                true
+            }
+            None => false,
+        }
+    }
+    //Runs on_drag_leave on the old drop target if it is replaced and returns the pressed object
+    //and the new drop target, if any. These are returned rather than borrowed from the
+    //DraggableSetHolder so that callbacks run on them can use the DragArea.
+    fn set_drop_target(
+        &self,
+        drag_info: &DragInfo,
+        new_target: Option<ItemId>,
+    ) -> Option<(SharedDraggable, SharedDraggable)> {
+        let old_target = drag_info.drop_target.replace(new_target);
+        let draggables = self.draggables.borrow();
+        let dragged = draggables.get_draggable(drag_info.id)?;
+        let old_target = old_target
+            .filter(|old_target| Some(*old_target) != new_target)
+            .and_then(|old_target| draggables.get_draggable(old_target));
+        let new_target = new_target.and_then(|new_target| draggables.get_draggable(new_target));
+        drop(draggables);
+        if let Some(old_target) = old_target {
+            old_target.borrow().on_drag_leave(&*dragged.borrow());
+        }
+        Some((dragged, new_target?))
+    }
+    fn move_dragged(
+        &self,
+        drag_info: &DragInfo,
+        x: f64,
+        y: f64,
+        modifiers: gdk::ModifierType,
+        releasing: bool,
+    ) {
+        let zoom = self.zoom.get();
+        let (mut dx, mut dy) = (x / zoom, y / zoom);
+        if let (Some(grid), Some((start_x, start_y))) = (self.grid.get(), drag_info.start()) {
+            let snap = match grid.mode {
+                SnapMode::Continuous => true,
+                SnapMode::OnRelease => releasing,
+            };
+            if snap && !modifiers.intersects(grid.bypass_modifiers) {
+                let (snapped_x, snapped_y) = grid.snap(start_x + dx, start_y + dy);
+                dx = snapped_x - start_x;
+                dy = snapped_y - start_y;
+            }
+        }
+        let alignment_guides = self
+            .alignment_guides
+            .get()
+            .filter(|alignment_guides| !modifiers.intersects(alignment_guides.bypass_modifiers));
+        let alignment_guides = match (alignment_guides, drag_info.start()) {
+            (Some(alignment_guides), Some((start_x, start_y))) => {
+                Some((alignment_guides, start_x, start_y))
+            }
+            _ => None,
+        };
+        if let Some((alignment_guides, start_x, start_y)) = alignment_guides {
+            if let Some((moving, others)) = self.guide_bounds(drag_info, start_x + dx, start_y + dy)
+            {
+                let (offset_x, offset_y) = guides::align(
+                    moving,
+                    &others,
+                    alignment_guides.threshold,
+                    alignment_guides.equal_spacing,
+                );
+                dx += offset_x;
+                dy += offset_y;
+            }
+        }
+        //Holding Shift keeps the objects on whichever axis the pointer has moved farther along.
+        if self.shift_axis_lock.get() && modifiers.contains(gdk::ModifierType::SHIFT_MASK) {
+            if x.abs() >= y.abs() {
+                dy = 0.0;
+            } else {
+                dx = 0.0;
+            }
+        }
+        self.move_group(&drag_info.items, dx, dy);
+        //Find the guides where the object actually ended up, which may differ from where it was
+        //aligned if it was kept inside the DragArea.
+        let mut new_guides = Vec::new();
+        if let Some((alignment_guides, _, _)) = alignment_guides {
+            if let Some((x, y)) = self.position(drag_info.id) {
+                if let Some((moving, others)) = self.guide_bounds(drag_info, x, y) {
+                    new_guides = guides::guides(moving, &others, alignment_guides.equal_spacing);
+                }
+            }
+        }
+        *self.guides.borrow_mut() = new_guides;
+    }
+    //The bounds of the pressed object if it were at the given coordinates and those of every other
+    //visible object that is not being dragged.
+    fn guide_bounds(
+        &self,
+        drag_info: &DragInfo,
+        x: f64,
+        y: f64,
+    ) -> Option<(guides::Bounds, Vec<guides::Bounds>)> {
+        let draggables = self.draggables.borrow();
+        let moving = guides::Bounds::new(
+            x,
+            y,
+            draggables
+                .get(drag_info.id)?
+                .draggable
+                .borrow()
+                .get_limits(),
+        );
+        let others = draggables
+            .iter_visible()
+            .filter(|draggable_and_coords| {
+                !drag_info
+                    .items
+                    .iter()
+                    .any(|(id, _, _)| *id == draggable_and_coords.id)
+            })
+            .map(|draggable_and_coords| {
+                guides::Bounds::new(
+                    draggable_and_coords.x,
+                    draggable_and_coords.y,
+                    draggable_and_coords.draggable.get_limits(),
+                )
+            })
+            .collect();
+        Some((moving, others))
+    }
+    //Moves objects from the given coordinates by the same offset, limiting the offset so that they
+    //keep their relative positions even when one of them reaches the edge.
+    fn move_group(&self, items: &[(ItemId, f64, f64)], dx: f64, dy: f64) {
+        let (mut dx, mut dy) = (dx, dy);
+        {
+            let draggables = self.draggables.borrow();
+            for (id, start_x, start_y) in items {
+                //The object may have been removed since the drag began.
+                if let Some(draggable_and_coords) = draggables.get(*id) {
+                    let limits = draggable_and_coords.draggable.borrow().get_limits();
+                    let (new_x, new_y) = self.clamp_position(limits, start_x + dx, start_y + dy);
+                    dx = new_x - start_x;
+                    dy = new_y - start_y;
+                }
+            }
+        }
+        for (id, start_x, start_y) in items {
+            //Bind this first so that constrain and can_move_to can use the DragArea.
+            let draggable = self
+                .draggables
+                .borrow()
+                .get(*id)
+                .map(|draggable_and_coords| {
+                    (
+                        draggable_and_coords.draggable.clone(),
+                        draggable_and_coords.constraint,
+                    )
+                });
+            if let Some((draggable, constraint)) = draggable {
+                let (new_x, new_y) = {
+                    let draggable = draggable.borrow();
+                    let (mut x, mut y) = (start_x + dx, start_y + dy);
+                    if let Some(constraint) = constraint {
+                        (x, y) = constraint.constrain(*start_x, *start_y, x, y);
+                    }
+                    let (x, y) = draggable.constrain(x, y);
+                    let (x, y) = self.clamp_position(draggable.get_limits(), x, y);
+                    match draggable.can_move_to(x, y) {
+                        Some((x, y)) => self.clamp_position(draggable.get_limits(), x, y),
+                        None => continue,
+                    }
+                };
+                if let Some(draggable_and_coords) = self.draggables.borrow_mut().get_mut(*id) {
+                    draggable_and_coords.x = new_x;
+                    draggable_and_coords.y = new_y;
+                }
+            }
+        }
+    }
+    //Emits item-moved for each of the given objects, given with their coordinates before moving,
+    //that actually moved.
+    fn emit_item_moved(&self, items: &[(ItemId, f64, f64)]) {
+        self.update_adjustments();
+        for (id, from_x, from_y) in items {
+            if let Some((x, y)) = self.position(*id) {
+                if (x, y) != (*from_x, *from_y) {
+                    self.emit_item_signal("item-moved", *id, x, y);
+                }
+            }
+        }
+    }
+    //Runs the click method of the interactive objects under the event, topmost first, passing each
+    //the event with coordinates relative to it, until one returns Propagation::Stop. Returns the
+    //object that did, or None if none did.
+    fn click(&self, click_type: ClickType, event: PointerEvent) -> Option<ItemId> {
+        let (x, y) = self.to_scene(event.widget_x, event.widget_y, Coordinates::Widget);
+        let clicked: Vec<(ItemId, SharedDraggable, f64, f64)> = self
+            .draggables
+            .borrow()
+            .hits(x, y)
+            .into_iter()
+            .map(|draggable_and_coords| {
+                (
+                    draggable_and_coords.id,
+                    draggable_and_coords.draggable.clone(),
+                    draggable_and_coords.x,
+                    draggable_and_coords.y,
+                )
+            })
+            .collect();
+        let mut stopped = None;
+        for (id, draggable, item_x, item_y) in clicked {
+            let event = PointerEvent {
+                x: x - item_x,
+                y: y - item_y,
+                ..event
+            };
+            let draggable = draggable.borrow();
+            let propagation = match click_type {
+                ClickType::Single => draggable.on_click(&event),
+                ClickType::Double => draggable.on_double_click(&event),
+                ClickType::Middle => draggable.on_middle_click(&event),
+                ClickType::Right => draggable.on_right_click(&event),
+            };
+            if let ClickType::Double = click_type {
+                self.emit_item_signal("item-activated", id, item_x, item_y);
+            }
+            if propagation.is_stop() {
+                stopped = Some(id);
+                break;
+            }
+        }
+        self.obj().queue_draw();
+        stopped
+    }
+    //Runs a function on each of the given objects that is still in the DragArea without the
+    //DraggableSetHolder borrowed so that the function can use the DragArea.
+    fn for_each_draggable(
+        &self,
+        ids: impl IntoIterator<Item = ItemId>,
+        mut func: impl FnMut(ItemId, &dyn Draggable),
+    ) {
+        for id in ids {
+            let draggable = self.draggables.borrow().get_draggable(id);
+            if let Some(draggable) = draggable {
+                func(id, &*draggable.borrow());
+            }
+        }
+    }
+    //Returns false if there is no marquee.
+    fn update_marquee(&self, width: f64, height: f64) -> bool {
+        let selection = {
+            let mut marquee = self.marquee.borrow_mut();
+            let marquee = match marquee.as_mut() {
+                Some(marquee) => marquee,
+                None => return false,
+            };
+            marquee.width = width;
+            marquee.height = height;
+            let rect = Rectangle::new(marquee.start_x, marquee.start_y, width, height);
+            let (left, top, right, bottom) = self.scene_rect(rect, Coordinates::Widget);
+            self.draggables.borrow().marquee_selection(
+                &marquee.base_selection,
+                left,
+                top,
+                right,
+                bottom,
+            )
+        };
+        let old_selection = self.selection.replace(selection);
+        self.emit_selection_changed(&old_selection);
+        true
+    }
+    //Ends a press given the offset from where it began.
+    fn drag_end(
+        &self,
+        sequence: Option<gdk::EventSequence>,
+        x: f64,
+        y: f64,
+        modifiers: gdk::ModifierType,
+    ) {
+        let press = {
+            let mut presses = self.presses.borrow_mut();
+            match presses.iter().position(|press| press.sequence == sequence) {
+                Some(index) => presses.remove(index),
+                None => return,
+            }
+        };
+        self.record_drag_sample(sequence.clone(), x, y);
+        let velocity = self.release_velocity(sequence);
+        let inertia = self.inertia.get();
+        let panned = press.drag_info.is_none()
+            && self.marquee.borrow().is_none()
+            && self.scrollable.get()
+            && self.scrolling.get();
+        if let Some(drag_info) = press.drag_info {
+            self.move_dragged(&drag_info, x, y, modifiers, true);
+            let dropped = self.drop_dragged(&drag_info, x, y);
+            match (inertia, velocity) {
+                (Some(inertia), Some((velocity_x, velocity_y))) if inertia.throwing && !dropped => {
+                    let zoom = self.zoom.get();
+                    let snap = self
+                        .grid
+                        .get()
+                        .is_some_and(|grid| !modifiers.intersects(grid.bypass_modifiers));
+                    self.throws.borrow_mut().push(Throw {
+                        drag_info,
+                        velocity_x: velocity_x / zoom,
+                        velocity_y: velocity_y / zoom,
+                        snap,
+                    });
+                    self.start_inertia();
+                }
+                _ => self.finish_drag(&drag_info),
+            }
+        }
+        let (old_trans_x, old_trans_y) = self.translate.get();
+        let (drag_trans_x, drag_trans_y) = self.drag_translate.get();
+        self.translate
+            .set((old_trans_x + drag_trans_x, old_trans_y + drag_trans_y));
+        self.drag_translate.set((0.0, 0.0));
+        let (trans_x, trans_y) = self.translate.get();
+        if self.scrollable.get()
+            && self.clamp_scroll_location(trans_x, trans_y) != (trans_x, trans_y)
+        {
+            self.spring_back();
+        } else if let (true, Some(inertia), Some(velocity)) = (panned, inertia, velocity) {
+            if inertia.panning {
+                self.kinetic_pan.set(Some(velocity));
+                self.start_inertia();
+            }
+        }
+        *self.marquee.borrow_mut() = None;
+        self.guides.borrow_mut().clear();
+        self.update_hover(self.pointer.get());
+        self.obj().queue_draw();
+        if let Some((start_x, start_y)) = self.background_press.take() {
+            //Presses moving less than GTK's drag threshold are clicks, allowing for jitter.
+            let threshold = self.obj().settings().gtk_dnd_drag_threshold() as f64;
+            if x.hypot(y) <= threshold {
+                let (x, y) = self.to_scene(start_x, start_y, Coordinates::Widget);
+                self.obj()
+                    .emit_by_name::<()>("background-clicked", &[&x, &y]);
+            }
+        }
+    }
+    //Snaps thrown objects to the grid if the drag would have snapped them on release, then finishes
+    //the drag.
+    fn finish_throw(&self, throw: Throw) {
+        let drag_info = throw.drag_info;
+        if let (true, Some(grid), Some((x, y))) =
+            (throw.snap, self.grid.get(), self.position(drag_info.id))
+        {
+            let items: Vec<(ItemId, f64, f64)> = drag_info
+                .items
+                .iter()
+                .filter_map(|(id, _, _)| self.position(*id).map(|(x, y)| (*id, x, y)))
+                .collect();
+            let (snapped_x, snapped_y) = grid.snap(x, y);
+            self.move_group(&items, snapped_x - x, snapped_y - y);
+            let dragged: Vec<ItemId> = items.iter().map(|(id, _, _)| *id).collect();
+            self.for_each_draggable(dragged, |id, draggable| {
+                if let Some((x, y)) = self.position(id) {
+                    draggable.on_drag_motion(x, y);
+                }
+            });
+        }
+        self.finish_drag(&drag_info);
+    }
+    //Runs on_drag_end on the dragged objects and emits item-moved for them. This is delayed until
+    //thrown objects stop.
+    fn finish_drag(&self, drag_info: &DragInfo) {
+        for (id, from_x, from_y) in &drag_info.items {
+            let draggable = self.draggables.borrow().get_draggable(*id);
+            if let (Some(draggable), Some(to)) = (draggable, self.position(*id)) {
+                draggable.borrow().on_drag_end((*from_x, *from_y), to);
+            }
+        }
+        self.emit_item_moved(&drag_info.items);
+    }
+    //Ends a press that GTK cancelled, e.g., because another gesture claimed it, without committing
+    //anything. Dragged objects are put back where they started and are not dropped or thrown, but
+    //their drags still end, and a pan or selection rectangle is undone.
+    fn drag_cancel(&self, sequence: Option<gdk::EventSequence>) {
+        let press = {
+            let mut presses = self.presses.borrow_mut();
+            match presses.iter().position(|press| press.sequence == sequence) {
+                Some(index) => presses.remove(index),
+                None => return,
+            }
+        };
+        self.drag_samples
             .borrow_mut()
-            .push((item as Rc<RefCell<dyn Draggable>>).into(), x, y);
+            .retain(|drag_sample| drag_sample.sequence != sequence);
+        match press.drag_info {
+            Some(drag_info) => {
+                self.set_drop_target(&drag_info, None);
+                for (id, start_x, start_y) in &drag_info.items {
+                    if let Some(draggable_and_coords) = self.draggables.borrow_mut().get_mut(*id) {
+                        draggable_and_coords.x = *start_x;
+                        draggable_and_coords.y = *start_y;
+                    }
+                }
+                self.for_each_draggable(
+                    drag_info.items.iter().map(|(id, _, _)| *id),
+                    |id, draggable| {
+                        if let Some((x, y)) = self.position(id) {
+                            draggable.on_drag_motion(x, y);
+                        }
+                    },
+                );
+                //End the drags begun with on_drag_begin. The objects are back where they began.
+                for (id, start_x, start_y) in &drag_info.items {
+                    let draggable = self.draggables.borrow().get_draggable(*id);
+                    if let Some(draggable) = draggable {
+                        draggable
+                            .borrow()
+                            .on_drag_end((*start_x, *start_y), (*start_x, *start_y));
+                    }
+                }
+            }
+            None => {
+                if self.drag_translate.replace((0.0, 0.0)) != (0.0, 0.0) {
+                    self.emit_scroll_changed();
+                }
+                if let Some(marquee) = self.marquee.take() {
+                    let old_selection = self.selection.replace(marquee.base_selection);
+                    self.emit_selection_changed(&old_selection);
+                }
+            }
+        }
+        self.background_press.set(None);
+        self.guides.borrow_mut().clear();
+        self.update_adjustments();
+        self.update_hover(self.pointer.get());
+        self.obj().queue_draw();
+    }
+    fn record_drag_sample(&self, sequence: Option<gdk::EventSequence>, x: f64, y: f64) {
+        let time = glib::monotonic_time();
+        let mut drag_samples = self.drag_samples.borrow_mut();
+        drag_samples.retain(|drag_sample| {
+            drag_sample.sequence != sequence || time - drag_sample.time <= VELOCITY_WINDOW
+        });
+        drag_samples.push(DragSample {
+            sequence,
+            time,
+            x,
+            y,
+        });
+    }
+    //Estimates the velocity of the pointer or finger in pixels per second from its recent drag
+    //samples, returning None if it is too slow for inertia.
+    fn release_velocity(&self, sequence: Option<gdk::EventSequence>) -> Option<(f64, f64)> {
+        let mut drag_samples = self.drag_samples.borrow_mut();
+        let samples: Vec<(i64, f64, f64)> = drag_samples
+            .iter()
+            .filter(|drag_sample| drag_sample.sequence == sequence)
+            .map(|drag_sample| (drag_sample.time, drag_sample.x, drag_sample.y))
+            .collect();
+        drag_samples.retain(|drag_sample| drag_sample.sequence != sequence);
+        drop(drag_samples);
+        let (first_time, first_x, first_y) = *samples.first()?;
+        let (last_time, last_x, last_y) = *samples.last()?;
+        if last_time == first_time {
+            return None;
+        }
+        let elapsed = (last_time - first_time) as f64 / 1_000_000.0;
+        let (velocity_x, velocity_y) = ((last_x - first_x) / elapsed, (last_y - first_y) / elapsed);
+        (velocity_x.hypot(velocity_y) >= MIN_INERTIA_SPEED).then_some((velocity_x, velocity_y))
+    }
+    fn start_inertia(&self) {
+        if self.inertia_running.replace(true) {
+            return;
+        }
+        let last_frame_time = Cell::new(None);
+        self.obj().add_tick_callback(move |obj, frame_clock| {
+            let self_imp = DragArea::from_obj(obj);
+            let frame_time = frame_clock.frame_time();
+            //In seconds.
+            let elapsed = match last_frame_time.replace(Some(frame_time)) {
+                Some(last_frame_time) => (frame_time - last_frame_time) as f64 / 1_000_000.0,
+                None => 0.0,
+            };
+            if self_imp.inertia_tick(elapsed) {
+                glib::ControlFlow::Continue
+            } else {
+                self_imp.inertia_running.set(false);
+                glib::ControlFlow::Break
+            }
+        });
+    }
+    //Moves the DragArea and thrown objects on by one frame, returning whether either is still
+    //moving. Their velocities are taken from how far they actually moved so that they stop at
+    //scroll limits and bounds and follow constraints.
+    fn inertia_tick(&self, elapsed: f64) -> bool {
+        let friction = match self.inertia.get() {
+            Some(inertia) => inertia.friction,
+            None => {
+                self.stop_inertia();
+                return false;
+            }
+        };
+        let decay = (-friction * elapsed).exp();
+        let velocity_after = |moved: f64, velocity: f64| {
+            if elapsed > 0.0 {
+                moved / elapsed * decay
+            } else {
+                velocity
+            }
+        };
+        let mut moving = false;
+        if let Some((velocity_x, velocity_y)) = self.kinetic_pan.get() {
+            let (x, y) = self.translate.get();
+            let (new_x, new_y) =
+                self.clamp_scroll_location(x + velocity_x * elapsed, y + velocity_y * elapsed);
+            self.translate.set((new_x, new_y));
+            self.emit_scroll_changed();
+            let velocity_x = velocity_after(new_x - x, velocity_x);
+            let velocity_y = velocity_after(new_y - y, velocity_y);
+            if velocity_x.hypot(velocity_y) < MIN_INERTIA_SPEED {
+                self.kinetic_pan.set(None);
+            } else {
+                self.kinetic_pan.set(Some((velocity_x, velocity_y)));
+                moving = true;
+            }
+        }
+        let mut still_thrown = Vec::new();
+        for mut throw in self.throws.take() {
+            let (drag_info, velocity_x, velocity_y) =
+                (&throw.drag_info, throw.velocity_x, throw.velocity_y);
+            let items: Vec<(ItemId, f64, f64)> = drag_info
+                .items
+                .iter()
+                .filter_map(|(id, _, _)| self.position(*id).map(|(x, y)| (*id, x, y)))
+                .collect();
+            let before = self.position(drag_info.id);
+            self.move_group(&items, velocity_x * elapsed, velocity_y * elapsed);
+            let dragged: Vec<ItemId> = items.iter().map(|(id, _, _)| *id).collect();
+            self.for_each_draggable(dragged, |id, draggable| {
+                if let Some((x, y)) = self.position(id) {
+                    draggable.on_drag_motion(x, y);
+                }
+            });
+            let velocity = match (before, self.position(drag_info.id)) {
+                (Some((old_x, old_y)), Some((new_x, new_y))) => (
+                    velocity_after(new_x - old_x, velocity_x),
+                    velocity_after(new_y - old_y, velocity_y),
+                ),
+                //The pressed object was removed.
+                _ => (0.0, 0.0),
+            };
+            let zoom = self.zoom.get();
+            if velocity.0.hypot(velocity.1) * zoom < MIN_INERTIA_SPEED {
+                self.finish_throw(throw);
+            } else {
+                (throw.velocity_x, throw.velocity_y) = velocity;
+                still_thrown.push(throw);
+                moving = true;
+            }
+        }
+        self.throws.borrow_mut().extend(still_thrown);
+        self.update_hover(self.pointer.get());
+        self.obj().queue_draw();
+        moving
+    }
+    pub fn set_inertia(&self, inertia: Option<Inertia>) {
+        if let Some(inertia) = inertia {
+            //Without friction, the motion would never stop.
+            assert!(
+                inertia.friction > 0.0,
+                "inertia friction must be greater than 0, got {}",
+                inertia.friction
+            );
+        }
+        self.inertia.set(inertia);
+        if inertia.is_none() {
+            self.stop_inertia();
+        }
+    }
+    //Stops the DragArea scrolling on after a pan and finishes the drags of thrown objects where
+    //they are, snapping them to the grid if they would have snapped on release.
+    fn stop_inertia(&self) {
+        self.kinetic_pan.set(None);
+        for throw in self.throws.take() {
+            self.finish_throw(throw);
+        }
+        self.obj().queue_draw();
+    }
+    pub fn inertia(&self) -> Option<Inertia> {
+        self.inertia.get()
+    }
+    //Passes touch events to drag_begin, drag_update, drag_end, and drag_cancel with their
+    //sequences.
+    fn touch_event(&self, event: &gdk::Event) -> glib::Propagation {
+        let event_type = event.event_type();
+        if !matches!(
+            event_type,
+            gdk::EventType::TouchBegin
+                | gdk::EventType::TouchUpdate
+                | gdk::EventType::TouchEnd
+                | gdk::EventType::TouchCancel
+        ) {
+            return glib::Propagation::Proceed;
+        }
+        let sequence = Some(event.event_sequence());
+        if event_type == gdk::EventType::TouchCancel {
+            self.drag_cancel(sequence);
+            return glib::Propagation::Proceed;
+        }
+        let (x, y) = match self.event_position(event) {
+            Some(position) => position,
+            None => return glib::Propagation::Proceed,
+        };
+        let modifiers = event.modifier_state();
+        if event_type == gdk::EventType::TouchBegin {
+            let device = event.device().map(|device| device.source());
+            self.drag_begin(sequence, x, y, modifiers, device);
+            return glib::Propagation::Proceed;
+        }
+        let start = self
+            .presses
+            .borrow()
+            .iter()
+            .find(|press| press.sequence == sequence)
+            .map(|press| (press.start_x, press.start_y));
+        if let Some((start_x, start_y)) = start {
+            let (x, y) = (x - start_x, y - start_y);
+            if event_type == gdk::EventType::TouchUpdate {
+                self.drag_update(sequence, x, y, modifiers);
+            } else {
+                self.drag_end(sequence, x, y, modifiers);
+            }
+        }
+        glib::Propagation::Proceed
+    }
+    //The position of an event in widget coordinates. Events given to an EventControllerLegacy
+    //are positioned relative to the surface.
+    fn event_position(&self, event: &gdk::Event) -> Option<(f64, f64)> {
+        let (x, y) = event.position()?;
+        let obj = self.obj();
+        let native = obj.native()?;
+        let (surface_x, surface_y) = native.surface_transform();
+        let point = native.compute_point(
+            &*obj,
+            &graphene::Point::new((x - surface_x) as f32, (y - surface_y) as f32),
+        )?;
+        Some((point.x() as f64, point.y() as f64))
+    }
+    fn draw_guides(&self, context: &Context) -> Result<(), Error> {
+        context.set_source_rgb(1.0, 0.0, 0.5);
+        context.set_line_width(1.0);
+        for guide in self.guides.borrow().iter() {
+            match guide.kind {
+                guides::GuideKind::Alignment => context.set_dash(&[], 0.0),
+                guides::GuideKind::Spacing => context.set_dash(&[4.0, 2.0], 0.0),
+            }
+            let (x0, y0) = self.to_widget(guide.x0, guide.y0);
+            let (x1, y1) = self.to_widget(guide.x1, guide.y1);
+            context.move_to(x0, y0);
+            context.line_to(x1, y1);
+            context.stroke()?;
+        }
+        context.set_dash(&[], 0.0);
+        Ok(())
+    }
+    fn draw_marquee(&self, context: &Context) -> Result<(), Error> {
+        if let Some(marquee) = self.marquee.borrow().as_ref() {
+            context.rectangle(
+                marquee.start_x,
+                marquee.start_y,
+                marquee.width,
+                marquee.height,
+            );
+            context.set_source_rgba(0.2, 0.4, 1.0, 0.2);
+            context.fill_preserve()?;
+            context.set_source_rgba(0.2, 0.4, 1.0, 0.8);
+            context.set_line_width(1.0);
+            context.stroke()?;
+        }
+        Ok(())
     }
     pub fn get_scroll_location(&self) -> (f64, f64) {
         let (trans_x, trans_y) = self.translate.get();
         let (drag_trans_x, drag_trans_y) = self.drag_translate.get();
         (trans_x + drag_trans_x, trans_y + drag_trans_y)
     }
+    pub fn set_scroll_limits(&self, scroll_limits: Option<ScrollLimits>) {
+        self.scroll_limits.set(scroll_limits);
+        self.update_adjustments();
+    }
+    pub fn scroll_limits(&self) -> Option<ScrollLimits> {
+        self.scroll_limits.get()
+    }
+    pub fn set_overscroll(&self, overscroll: bool) {
+        self.overscroll.set(overscroll);
+    }
+    //The rectangle in scene coordinates that the shown part of the scene is kept inside, if any.
+    fn scroll_limits_rect(&self) -> Option<Rectangle> {
+        match self.scroll_limits.get()? {
+            ScrollLimits::Rectangle(rect) => Some(rect),
+            ScrollLimits::Content { margin } => self.content_rect(margin),
+        }
+    }
+    //The smallest rectangle containing every visible object, extended by margin on every side, or
+    //None if there are no visible objects.
+    fn content_rect(&self, margin: f64) -> Option<Rectangle> {
+        let draggables = self.draggables.borrow();
+        let mut content: Option<(f64, f64, f64, f64)> = None;
+        for draggable_and_coords in draggables.iter_visible() {
+            let (neg_x, pos_x, neg_y, pos_y) = draggable_and_coords.draggable.get_limits();
+            let (x, y) = (draggable_and_coords.x, draggable_and_coords.y);
+            let (left, right, top, bottom) = (x - neg_x, x + pos_x, y - neg_y, y + pos_y);
+            content = Some(match content {
+                Some((old_left, old_right, old_top, old_bottom)) => (
+                    old_left.min(left),
+                    old_right.max(right),
+                    old_top.min(top),
+                    old_bottom.max(bottom),
+                ),
+                None => (left, right, top, bottom),
+            });
+        }
+        let (left, right, top, bottom) = content?;
+        Some(Rectangle::new(
+            left - margin,
+            top - margin,
+            right - left + 2.0 * margin,
+            bottom - top + 2.0 * margin,
+        ))
+    }
+    fn set_adjustment(&self, orientation: Orientation, adjustment: Option<Adjustment>) {
+        let cell = match orientation {
+            Orientation::Horizontal => &self.hadjustment,
+            _ => &self.vadjustment,
+        };
+        if let Some((old, handler)) = cell.borrow_mut().take() {
+            old.disconnect(handler);
+        }
+        if let Some(adjustment) = adjustment {
+            let my_obj = self.obj().downgrade();
+            let handler = adjustment.connect_value_changed(move |_| {
+                if let Some(obj) = my_obj.upgrade() {
+                    DragArea::from_obj(&obj).adjustment_value_changed();
+                }
+            });
+            *cell.borrow_mut() = Some((adjustment, handler));
+        }
+        self.update_adjustments();
+    }
+    fn adjustment(&self, orientation: Orientation) -> Option<Adjustment> {
+        let cell = match orientation {
+            Orientation::Horizontal => &self.hadjustment,
+            _ => &self.vadjustment,
+        };
+        cell.borrow()
+            .as_ref()
+            .map(|(adjustment, _)| adjustment.clone())
+    }
+    //Sets the adjustments from the Scrollable interface to match the scroll location, the size of
+    //the DragArea, and the extent of the objects or scroll limits.
+    fn update_adjustments(&self) {
+        if self.updating_adjustments.replace(true) {
+            return;
+        }
+        let obj = self.obj();
+        let (scroll_x, scroll_y) = self.get_scroll_location();
+        let zoom = self.zoom.get();
+        let extent = if self.scrollable.get() {
+            self.scroll_limits_rect().or_else(|| self.content_rect(0.0))
+        } else {
+            None
+        };
+        let step = self.wheel_scroll_step.get();
+        let configure = |adjustment: Option<Adjustment>, view_start: f64, size: f64, extent| {
+            let adjustment = match adjustment {
+                Some(adjustment) => adjustment,
+                None => return,
+            };
+            let (lower, upper) = match extent {
+                Some((start, end)) => (
+                    f64::min(start * zoom, view_start),
+                    f64::max(end * zoom, view_start + size),
+                ),
+                None => (view_start, view_start + size),
+            };
+            adjustment.configure(view_start, lower, upper, step, size * 0.9, size);
+        };
+        configure(
+            self.adjustment(Orientation::Horizontal),
+            -scroll_x,
+            obj.width() as f64,
+            extent.map(|rect| {
+                (
+                    rect.x.min(rect.x + rect.width),
+                    rect.x.max(rect.x + rect.width),
+                )
+            }),
+        );
+        configure(
+            self.adjustment(Orientation::Vertical),
+            -scroll_y,
+            obj.height() as f64,
+            extent.map(|rect| {
+                (
+                    rect.y.min(rect.y + rect.height),
+                    rect.y.max(rect.y + rect.height),
+                )
+            }),
+        );
+        self.updating_adjustments.set(false);
+    }
+    //Scrolls to where the adjustments from the Scrollable interface were set, e.g., by the
+    //scrollbars of a ScrolledWindow.
+    fn adjustment_value_changed(&self) {
+        if self.updating_adjustments.get() || !self.scrollable.get() {
+            return;
+        }
+        let (scroll_x, scroll_y) = self.get_scroll_location();
+        let scroll_x = self
+            .adjustment(Orientation::Horizontal)
+            .map_or(scroll_x, |adjustment| -adjustment.value());
+        let scroll_y = self
+            .adjustment(Orientation::Vertical)
+            .map_or(scroll_y, |adjustment| -adjustment.value());
+        let (drag_trans_x, drag_trans_y) = self.drag_translate.get();
+        self.springing_back.set(false);
+        self.kinetic_pan.set(None);
+        self.translate
+            .set((scroll_x - drag_trans_x, scroll_y - drag_trans_y));
+        self.emit_scroll_changed();
+        self.update_hover(self.pointer.get());
+        self.obj().queue_draw();
+    }
+    //Returns the nearest scroll location to the given one allowed by the scroll limits.
+    fn clamp_scroll_location(&self, x: f64, y: f64) -> (f64, f64) {
+        let rect = match self.scroll_limits_rect() {
+            Some(rect) => rect,
+            None => return (x, y),
+        };
+        let obj = self.obj();
+        let zoom = self.zoom.get();
+        (
+            clamp_scroll(
+                x,
+                rect.x.min(rect.x + rect.width) * zoom,
+                rect.x.max(rect.x + rect.width) * zoom,
+                obj.width() as f64,
+            ),
+            clamp_scroll(
+                y,
+                rect.y.min(rect.y + rect.height) * zoom,
+                rect.y.max(rect.y + rect.height) * zoom,
+                obj.height() as f64,
+            ),
+        )
+    }
+    //Like clamp_scroll_location, but lets the location go past the limits with increasing
+    //resistance if overscroll is enabled.
+    fn overscroll_location(&self, x: f64, y: f64) -> (f64, f64) {
+        let (clamped_x, clamped_y) = self.clamp_scroll_location(x, y);
+        if !self.overscroll.get() {
+            return (clamped_x, clamped_y);
+        }
+        (
+            clamped_x + rubber_band(x - clamped_x),
+            clamped_y + rubber_band(y - clamped_y),
+        )
+    }
+    //Animates scrolling back inside the scroll limits.
+    fn spring_back(&self) {
+        if self.springing_back.replace(true) {
+            return;
+        }
+        let last_frame_time = Cell::new(None);
+        self.obj().add_tick_callback(move |obj, frame_clock| {
+            let self_imp = DragArea::from_obj(obj);
+            if !self_imp.springing_back.get() {
+                return glib::ControlFlow::Break;
+            }
+            let frame_time = frame_clock.frame_time();
+            //In seconds.
+            let elapsed = match last_frame_time.replace(Some(frame_time)) {
+                Some(last_frame_time) => (frame_time - last_frame_time) as f64 / 1_000_000.0,
+                None => 0.0,
+            };
+            let (x, y) = self_imp.translate.get();
+            let (target_x, target_y) = self_imp.clamp_scroll_location(x, y);
+            let progress = 1.0 - (-elapsed / SPRING_BACK_TIME).exp();
+            let (x, y) = (x + (target_x - x) * progress, y + (target_y - y) * progress);
+            let done = (target_x - x).abs() < 0.5 && (target_y - y).abs() < 0.5;
+            self_imp
+                .translate
+                .set(if done { (target_x, target_y) } else { (x, y) });
+            self_imp.emit_scroll_changed();
+            obj.queue_draw();
+            if done {
+                self_imp.springing_back.set(false);
+                glib::ControlFlow::Break
+            } else {
+                glib::ControlFlow::Continue
+            }
+        });
+    }
+    pub fn set_zoom(&self, zoom: f64) {
+        //Clamping to the zoom limits would let NaN through.
+        assert!(zoom.is_finite(), "zoom must be finite, got {zoom}");
+        let obj = self.obj();
+        self.zoom_around(zoom, obj.width() as f64 / 2.0, obj.height() as f64 / 2.0);
+    }
+    pub fn zoom(&self) -> f64 {
+        self.zoom.get()
+    }
+    pub fn set_zoom_limits(&self, min: f64, max: f64) {
+        //A zoom of 0 would make to_scene divide by zero.
+        assert!(
+            min.is_finite() && max.is_finite() && 0.0 < min && min <= max,
+            "zoom limits must be finite with 0 < min <= max, got {min} and {max}"
+        );
+        self.zoom_limits.set((min, max));
+        self.set_zoom(self.zoom.get());
+    }
+    pub fn set_zoomable(&self, zoomable: bool) {
+        self.zoomable.set(zoomable);
+    }
+    //Zooms keeping the given point in widget coordinates over the same point in the scene if the
+    //DragArea is scrollable. Otherwise, the top left corner stays where it is.
+    fn zoom_around(&self, zoom: f64, x: f64, y: f64) {
+        let (min, max) = self.zoom_limits.get();
+        let zoom = zoom.clamp(min, max);
+        if zoom == self.zoom.get() {
+            return;
+        }
+        let scrollable = self.scrollable.get();
+        if scrollable {
+            let (scene_x, scene_y) = self.to_scene(x, y, Coordinates::Widget);
+            let (drag_trans_x, drag_trans_y) = self.drag_translate.get();
+            self.translate.set((
+                x - scene_x * zoom - drag_trans_x,
+                y - scene_y * zoom - drag_trans_y,
+            ));
+        }
+        self.zoom.set(zoom);
+        self.obj().emit_by_name::<()>("zoom-changed", &[&zoom]);
+        if scrollable {
+            self.emit_scroll_changed();
+            let (trans_x, trans_y) = self.translate.get();
+            if self.presses.borrow().is_empty()
+                && self.clamp_scroll_location(trans_x, trans_y) != (trans_x, trans_y)
+            {
+                self.spring_back();
+            }
+        }
+        if self.keep_inside.get() {
+            self.push_inside();
+        }
+        self.update_hover(self.pointer.get());
+        self.obj().queue_draw();
+    }
+    //Zooms with Control and the mouse wheel, keeping the point under the pointer in place, or
+    //scrolls, horizontally if Shift is held.
+    fn scroll(
+        &self,
+        dx: f64,
+        dy: f64,
+        unit: gdk::ScrollUnit,
+        modifiers: gdk::ModifierType,
+    ) -> glib::Propagation {
+        //Touchpads give deltas in pixels, and mouse wheels give them in steps.
+        let step = self.wheel_scroll_step.get();
+        let (dx, dy) = match unit {
+            gdk::ScrollUnit::Wheel => (dx * step, dy * step),
+            _ => (dx, dy),
+        };
+        if self.zoomable.get() && modifiers.contains(gdk::ModifierType::CONTROL_MASK) {
+            let obj = self.obj();
+            let (x, y) = self
+                .pointer
+                .get()
+                .unwrap_or((obj.width() as f64 / 2.0, obj.height() as f64 / 2.0));
+            //Zoom by ZOOM_STEP for every wheel step's worth of scrolling.
+            let steps = if step > 0.0 { dy / step } else { 0.0 };
+            self.zoom_around(self.zoom.get() * ZOOM_STEP.powf(-steps), x, y);
+            return glib::Propagation::Stop;
+        }
+        //Leave scrolling to the ScrolledWindow, if any, so that it can scroll kinetically.
+        let has_adjustments =
+            self.hadjustment.borrow().is_some() || self.vadjustment.borrow().is_some();
+        if !(self.scrollable.get() && self.wheel_scrolling.get()) || has_adjustments {
+            return glib::Propagation::Proceed;
+        }
+        let (dx, dy) = if modifiers.contains(gdk::ModifierType::SHIFT_MASK) && dx == 0.0 {
+            (dy, 0.0)
+        } else {
+            (dx, dy)
+        };
+        //Stop springing back or moving on after a pan so that it does not fight the wheel.
+        self.springing_back.set(false);
+        self.kinetic_pan.set(None);
+        let (trans_x, trans_y) = self.translate.get();
+        let (drag_trans_x, drag_trans_y) = self.drag_translate.get();
+        let (new_x, new_y) =
+            self.clamp_scroll_location(trans_x + drag_trans_x - dx, trans_y + drag_trans_y - dy);
+        self.translate
+            .set((new_x - drag_trans_x, new_y - drag_trans_y));
+        self.emit_scroll_changed();
+        self.update_hover(self.pointer.get());
+        self.obj().queue_draw();
+        glib::Propagation::Stop
+    }
+    pub fn set_wheel_scrolling(&self, wheel_scrolling: bool) {
+        self.wheel_scrolling.set(wheel_scrolling);
+    }
+    pub fn set_wheel_scroll_step(&self, wheel_scroll_step: f64) {
+        self.wheel_scroll_step.set(wheel_scroll_step);
+    }
     pub fn set_scrollable(&self, scrollable: bool) {
         self.scrollable.set(scrollable);
     }
@@ -210,11 +2578,45 @@ impl Default for DragArea {
     fn default() -> Self {
         Self {
             draggables: Rc::new(RefCell::new(DraggableSetHolder::new())),
-            drag_info: Rc::new(RefCell::new(None)),
+            presses: Rc::new(RefCell::new(Vec::new())),
+            selection: Rc::new(RefCell::new(Vec::new())),
+            focused: Rc::new(Cell::new(None)),
+            hovered: Rc::new(Cell::new(None)),
+            pointer: Rc::new(Cell::new(None)),
+            nudge_step: Rc::new(Cell::new((1.0, 10.0))),
+            grid: Rc::new(Cell::new(None)),
+            alignment_guides: Rc::new(Cell::new(None)),
+            guides: Rc::new(RefCell::new(Vec::new())),
+            marquee: Rc::new(RefCell::new(None)),
+            background_press: Rc::new(Cell::new(None)),
+            last_press: Rc::new(Cell::new(None)),
             scrollable: Rc::new(Cell::new(false)),
             scrolling: Rc::new(Cell::new(false)),
+            raise_on_press: Rc::new(Cell::new(true)),
+            bounds: Rc::new(Cell::new(None)),
+            keep_inside: Rc::new(Cell::new(false)),
+            shift_axis_lock: Rc::new(Cell::new(true)),
             translate: Rc::new(Cell::new((0.0, 0.0))),
             drag_translate: Rc::new(Cell::new((0.0, 0.0))),
+            scroll_limits: Rc::new(Cell::new(None)),
+            hadjustment: Rc::new(RefCell::new(None)),
+            vadjustment: Rc::new(RefCell::new(None)),
+            hscroll_policy: Rc::new(Cell::new(ScrollablePolicy::Minimum)),
+            vscroll_policy: Rc::new(Cell::new(ScrollablePolicy::Minimum)),
+            updating_adjustments: Rc::new(Cell::new(false)),
+            wheel_scrolling: Rc::new(Cell::new(true)),
+            wheel_scroll_step: Rc::new(Cell::new(50.0)),
+            zoom: Rc::new(Cell::new(1.0)),
+            zoom_limits: Rc::new(Cell::new((0.1, 10.0))),
+            zoomable: Rc::new(Cell::new(false)),
+            pinch_zoom: Rc::new(Cell::new(1.0)),
+            overscroll: Rc::new(Cell::new(true)),
+            springing_back: Rc::new(Cell::new(false)),
+            inertia: Rc::new(Cell::new(None)),
+            drag_samples: Rc::new(RefCell::new(Vec::new())),
+            kinetic_pan: Rc::new(Cell::new(None)),
+            throws: Rc::new(RefCell::new(Vec::new())),
+            inertia_running: Rc::new(Cell::new(false)),
             pre_draw_func: Rc::new(RefCell::new(None)),
             post_draw_func: Rc::new(RefCell::new(None)),
         }
@@ -225,32 +2627,104 @@ impl ObjectSubclass for DragArea {
     const NAME: &'static str = "CairoDragDragArea";
     type Type = super::DragArea;
     type ParentType = DrawingArea;
+    type Interfaces = (Scrollable,);
+}
+//How much one step of the mouse wheel zooms by.
+const ZOOM_STEP: f64 = 1.1;
+//The time constant in seconds of the DragArea springing back inside its scroll limits.
+const SPRING_BACK_TIME: f64 = 0.08;
+//How far back in microseconds drag samples are used to estimate the velocity when a drag ends.
+const VELOCITY_WINDOW: i64 = 100_000;
+//The speed in pixels per second below which panning and thrown objects stop.
+const MIN_INERTIA_SPEED: f64 = 20.0;
+//The farthest the DragArea can be dragged past its scroll limits.
+const OVERSCROLL_DISTANCE: f64 = 100.0;
+//Given how far past its scroll limits the DragArea has been dragged, returns how far past them it
+//should be shown, which approaches OVERSCROLL_DISTANCE.
+fn rubber_band(excess: f64) -> f64 {
+    excess.signum() * OVERSCROLL_DISTANCE * (1.0 - 1.0 / (1.0 + excess.abs() / OVERSCROLL_DISTANCE))
+}
+//Clamps a scroll location on one axis so that the shown part of the scene, which starts at
+//-scroll, stays between start and end or, if it is larger, so that start to end stays shown.
+fn clamp_scroll(scroll: f64, start: f64, end: f64, area_size: f64) -> f64 {
+    let (a, b) = (-start, area_size - end);
+    scroll.clamp(a.min(b), a.max(b))
 }
 fn calculate_limits(
     neg_limit: f64,
     pos_limit: f64,
-    area_size: i32, //width or height, whichever we're calculating
-    scrollable: bool,
+    area_start: f64, //left or top, whichever we're calculating
+    area_end: f64,   //right or bottom
     desired_coord: f64,
 ) -> f64 {
-    if !scrollable {
-        if desired_coord < neg_limit {
-            return neg_limit;
-        }
-        if desired_coord > area_size as f64 - pos_limit {
-            return area_size as f64 - pos_limit;
-        }
+    if desired_coord < area_start + neg_limit {
+        return area_start + neg_limit;
+    }
+    if desired_coord > area_end - pos_limit {
+        return area_end - pos_limit;
     }
     desired_coord
 }
 impl ObjectImpl for DragArea {
+    fn properties() -> &'static [glib::ParamSpec] {
+        static PROPERTIES: OnceLock<Vec<glib::ParamSpec>> = OnceLock::new();
+        PROPERTIES.get_or_init(|| {
+            vec![
+                glib::ParamSpecOverride::for_interface::<Scrollable>("hadjustment"),
+                glib::ParamSpecOverride::for_interface::<Scrollable>("vadjustment"),
+                glib::ParamSpecOverride::for_interface::<Scrollable>("hscroll-policy"),
+                glib::ParamSpecOverride::for_interface::<Scrollable>("vscroll-policy"),
+            ]
+        })
+    }
+    fn set_property(&self, _id: usize, value: &glib::Value, pspec: &glib::ParamSpec) {
+        match pspec.name() {
+            "hadjustment" => self.set_adjustment(Orientation::Horizontal, value.get().unwrap()),
+            "vadjustment" => self.set_adjustment(Orientation::Vertical, value.get().unwrap()),
+            "hscroll-policy" => self.hscroll_policy.set(value.get().unwrap()),
+            "vscroll-policy" => self.vscroll_policy.set(value.get().unwrap()),
+            name => unreachable!("set_property called for unknown property {name}"),
+        }
+    }
+    fn property(&self, _id: usize, pspec: &glib::ParamSpec) -> glib::Value {
+        match pspec.name() {
+            "hadjustment" => self.adjustment(Orientation::Horizontal).to_value(),
+            "vadjustment" => self.adjustment(Orientation::Vertical).to_value(),
+            "hscroll-policy" => self.hscroll_policy.get().to_value(),
+            "vscroll-policy" => self.vscroll_policy.get().to_value(),
+            name => unreachable!("property called for unknown property {name}"),
+        }
+    }
+    fn signals() -> &'static [Signal] {
+        static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+        SIGNALS.get_or_init(|| {
+            let item = [u64::static_type(), f64::static_type(), f64::static_type()];
+            let point = [f64::static_type(), f64::static_type()];
+            vec![
+                Signal::builder("item-drag-begin").param_types(item).build(),
+                Signal::builder("item-moved").param_types(item).build(),
+                Signal::builder("item-activated").param_types(item).build(),
+                Signal::builder("item-removed").param_types(item).build(),
+                Signal::builder("selection-changed").build(),
+                Signal::builder("background-clicked")
+                    .param_types(point)
+                    .build(),
+                Signal::builder("scroll-changed").param_types(point).build(),
+                Signal::builder("zoom-changed")
+                    .param_types([f64::static_type()])
+                    .build(),
+            ]
+        })
+    }
     fn constructed(&self) {
         self.parent_constructed();
         let my_draggables = self.draggables.clone();
-        let my_translate = self.translate.clone();
-        let my_drag_translate = self.drag_translate.clone();
+        let my_selection = self.selection.clone();
+        let my_focused = self.focused.clone();
+        let my_hovered = self.hovered.clone();
         let my_pre_draw_func = self.pre_draw_func.clone();
         let my_post_draw_func = self.post_draw_func.clone();
+        let my_obj = self.obj().clone();
         self.obj()
             .set_draw_func(move |drawing_area, context, width, height| {
                 match &mut *my_pre_draw_func.borrow_mut() {
@@ -258,173 +2732,260 @@ impl ObjectImpl for DragArea {
                     Some(func) => (*func)(drawing_area, context, width, height),
                     None => (),
                 }
-                my_draggables.borrow_mut().retain();
-                for i in my_draggables.borrow().iter() {
-                    let (trans_x, trans_y) = my_translate.get();
-                    let (drag_trans_x, drag_trans_y) = my_drag_translate.get();
-                    let x = i.x + trans_x + drag_trans_x;
-                    let y = i.y + trans_y + drag_trans_y;
-                    i.draggable.draw(&context, x, y).unwrap();
+                let self_imp = DragArea::from_obj(&my_obj);
+                let removed = my_draggables.borrow_mut().retain();
+                self_imp.notify_removed(removed);
+                let has_focus = drawing_area.has_focus();
+                //Objects are drawn in scene coordinates.
+                let (scroll_x, scroll_y) = self_imp.get_scroll_location();
+                let zoom = self_imp.zoom.get();
+                context.save().unwrap();
+                context.translate(scroll_x, scroll_y);
+                context.scale(zoom, zoom);
+                for i in my_draggables.borrow().iter_visible() {
+                    let state = DrawState {
+                        selected: my_selection.borrow().contains(&i.id),
+                        focused: has_focus && my_focused.get() == Some(i.id),
+                        hovered: my_hovered.get() == Some(i.id),
+                    };
+                    i.draggable.draw(context, i.x, i.y, state).unwrap();
                 }
+                context.restore().unwrap();
+                self_imp.draw_guides(context).unwrap();
+                self_imp.draw_focus(context).unwrap();
+                self_imp.draw_marquee(context).unwrap();
                 match &mut *my_post_draw_func.borrow_mut() {
                     Some(func) => (*func)(drawing_area, context, width, height),
                     None => (),
                 }
             });
+        //Touches are handled by the EventControllerLegacy below so that each finger can drag.
         let drag = GestureDrag::new();
-        let my_draggables = self.draggables.clone();
-        let my_drag_info = self.drag_info.clone();
         let my_obj = self.obj().clone();
-        let my_scrolling = self.scrolling.clone();
-        let my_translate = self.translate.clone();
-        drag.connect_drag_begin(move |_gesture: &GestureDrag, x: f64, y: f64| {
-            //drag_translate is always (0.0, 0.0) when we're not actively dragging, which we're not
-            //when the drag begin function is called.
-            let (trans_x, trans_y) = my_translate.get();
-            let mut new_drag_info = None;
-            let mut scrolling = true;
-            for (i, draggable_and_coords) in my_draggables.borrow().iter().enumerate() {
-                if draggable_and_coords.draggable.contains(
-                    x - trans_x - draggable_and_coords.x,
-                    y - trans_y - draggable_and_coords.y,
-                ) {
-                    new_drag_info = Some(DragInfo {
-                        start_x: x,
-                        start_y: y,
-                        index: i,
-                        relative_x: draggable_and_coords.x - x,
-                        relative_y: draggable_and_coords.y - y,
-                    })
-                }
-                if !draggable_and_coords.draggable.can_scroll(
-                    x - trans_x - draggable_and_coords.x,
-                    y - trans_y - draggable_and_coords.y,
-                ) {
-                    scrolling = false;
-                }
+        drag.connect_drag_begin(move |gesture: &GestureDrag, x: f64, y: f64| {
+            let touch = gesture
+                .current_event_device()
+                .is_some_and(|device| device.source() == gdk::InputSource::Touchscreen);
+            if touch {
+                gesture.set_state(EventSequenceState::Denied);
+                return;
             }
-            new_drag_info = match new_drag_info {
-                Some(drag_info) => Some(DragInfo {
-                    start_x: drag_info.start_x,
-                    start_y: drag_info.start_y,
-                    index: my_draggables.borrow_mut().move_to_end(drag_info.index),
-                    relative_x: drag_info.relative_x,
-                    relative_y: drag_info.relative_y,
-                }),
-                None => None,
-            };
-            *my_drag_info.borrow_mut() = new_drag_info;
-            my_scrolling.set(scrolling);
-            my_obj.queue_draw();
+            DragArea::from_obj(&my_obj).drag_begin(
+                None,
+                x,
+                y,
+                gesture.current_event_state(),
+                gesture.current_event_device().map(|device| device.source()),
+            );
         });
-        let my_draggables = self.draggables.clone();
-        let my_drag_info = self.drag_info.clone();
         let my_obj = self.obj().clone();
-        let my_scrollable = self.scrollable.clone();
-        let my_scrolling = self.scrolling.clone();
-        let my_drag_translate = self.drag_translate.clone();
-        drag.connect_drag_update(move |_gesture: &GestureDrag, x: f64, y: f64| {
-            let scrollable = my_scrollable.get();
-            let scrolling = my_scrolling.get();
-            let binding = my_drag_info.borrow();
-            let my_real_drag_info = match binding.as_ref() {
-                Some(x) => x,
-                None => {
-                    if scrollable && scrolling {
-                        my_drag_translate.set((x, y));
-                        my_obj.queue_draw();
-                    }
-                    return;
-                }
-            };
-            let (neg_x_limit, pos_x_limit, neg_y_limit, pos_y_limit) =
-                my_draggables.borrow().draggables_and_locs[my_real_drag_info.index]
-                    .draggable
-                    .borrow()
-                    .get_limits();
-            my_draggables.borrow_mut().draggables_and_locs[my_real_drag_info.index].x =
-                calculate_limits(
-                    neg_x_limit,
-                    pos_x_limit,
-                    my_obj.property("width_request"),
-                    scrollable,
-                    my_real_drag_info.start_x + x + my_real_drag_info.relative_x,
-                );
-            my_draggables.borrow_mut().draggables_and_locs[my_real_drag_info.index].y =
-                calculate_limits(
-                    neg_y_limit,
-                    pos_y_limit,
-                    my_obj.property("height_request"),
-                    scrollable,
-                    my_real_drag_info.start_y + y + my_real_drag_info.relative_y,
-                );
-            my_obj.queue_draw();
+        drag.connect_drag_update(move |gesture: &GestureDrag, x: f64, y: f64| {
+            DragArea::from_obj(&my_obj).drag_update(None, x, y, gesture.current_event_state());
         });
         let my_obj = self.obj().clone();
-        let my_translate = self.translate.clone();
-        let my_drag_translate = self.drag_translate.clone();
-        //XXX: This does not update the position of anything
-        drag.connect_drag_end(move |_gesture: &GestureDrag, _x: f64, _y: f64| {
-            let (old_trans_x, old_trans_y) = my_translate.get();
-            let (drag_trans_x, drag_trans_y) = my_drag_translate.get();
-            //let (drag_trans_x, drag_trans_y) = (x, y);
-            let new_trans = (old_trans_x + drag_trans_x, old_trans_y + drag_trans_y);
-            my_translate.set(new_trans);
-            my_drag_translate.set((0.0, 0.0));
-            my_obj.queue_draw();
+        drag.connect_drag_end(move |gesture: &GestureDrag, x: f64, y: f64| {
+            DragArea::from_obj(&my_obj).drag_end(None, x, y, gesture.current_event_state());
+        });
+        let my_obj = self.obj().clone();
+        drag.connect_cancel(move |_, sequence| {
+            //Touches denied in drag_begin have sequences and are handled by touch_event.
+            if sequence.is_none() {
+                DragArea::from_obj(&my_obj).drag_cancel(None);
+            }
         });
         self.obj().add_controller(drag);
-        enum ClickType {
-            Double,
-            Middle,
-            Right,
-        }
-        let my_draggables = self.draggables.clone();
-        let my_translate = self.translate.clone();
+        let touch = EventControllerLegacy::new();
         let my_obj = self.obj().clone();
-        let click = move |click_type: ClickType, x: f64, y: f64| {
-            let (trans_x, trans_y) = my_translate.get();
-            for draggable_and_coords in my_draggables.borrow().iter() {
-                if draggable_and_coords.draggable.contains(
-                    x - trans_x - draggable_and_coords.x,
-                    y - trans_y - draggable_and_coords.y,
-                ) {
-                    match click_type {
-                        ClickType::Double => draggable_and_coords.draggable.on_double_click(),
-                        ClickType::Middle => draggable_and_coords.draggable.on_middle_click(),
-                        ClickType::Right => draggable_and_coords.draggable.on_right_click(),
-                    }
-                }
+        touch.connect_event(move |_, event| DragArea::from_obj(&my_obj).touch_event(event));
+        self.obj().add_controller(touch);
+        self.obj().set_focusable(true);
+        self.obj().connect_has_focus_notify(|obj| obj.queue_draw());
+        let key = EventControllerKey::new();
+        let my_obj = self.obj().clone();
+        key.connect_key_pressed(move |_, key, _, modifiers| {
+            DragArea::from_obj(&my_obj).key_pressed(key, modifiers)
+        });
+        self.obj().add_controller(key);
+        let motion = EventControllerMotion::new();
+        let my_obj = self.obj().clone();
+        motion.connect_enter(move |_, x, y| {
+            DragArea::from_obj(&my_obj).update_hover(Some((x, y)));
+        });
+        let my_obj = self.obj().clone();
+        motion.connect_motion(move |_, x, y| {
+            DragArea::from_obj(&my_obj).update_hover(Some((x, y)));
+        });
+        let my_obj = self.obj().clone();
+        motion.connect_leave(move |_| {
+            DragArea::from_obj(&my_obj).update_hover(None);
+        });
+        self.obj().add_controller(motion);
+        let scroll = EventControllerScroll::new(EventControllerScrollFlags::BOTH_AXES);
+        let my_obj = self.obj().clone();
+        scroll.connect_scroll(move |controller, dx, dy| {
+            DragArea::from_obj(&my_obj).scroll(
+                dx,
+                dy,
+                controller.unit(),
+                controller.current_event_state(),
+            )
+        });
+        self.obj().add_controller(scroll);
+        let pinch = GestureZoom::new();
+        let my_obj = self.obj().clone();
+        pinch.connect_begin(move |_, _| {
+            let self_imp = DragArea::from_obj(&my_obj);
+            self_imp.pinch_zoom.set(self_imp.zoom.get());
+        });
+        let my_obj = self.obj().clone();
+        pinch.connect_scale_changed(move |gesture, scale| {
+            let self_imp = DragArea::from_obj(&my_obj);
+            //Several fingers dragging objects are not a pinch.
+            let dragging = self_imp
+                .presses
+                .borrow()
+                .iter()
+                .any(|press| press.drag_info.is_some());
+            if let (true, false, Some((x, y))) = (
+                self_imp.zoomable.get(),
+                dragging,
+                gesture.bounding_box_center(),
+            ) {
+                self_imp.zoom_around(self_imp.pinch_zoom.get() * scale, x, y);
             }
-            my_obj.queue_draw();
+        });
+        self.obj().add_controller(pinch);
+        let my_obj = self.obj().clone();
+        let click = move |click_type: ClickType, gesture: &GestureClick, clicks, x, y| {
+            let event = PointerEvent {
+                x: 0.0,
+                y: 0.0,
+                widget_x: x,
+                widget_y: y,
+                button: gesture.current_button(),
+                click_count: clicks,
+                modifiers: gesture.current_event_state(),
+                device: gesture.current_event_device().map(|device| device.source()),
+            };
+            DragArea::from_obj(&my_obj).click(click_type, event)
         };
         let left_click = GestureClick::new();
         left_click.set_button(1);
         let my_click = click.clone();
-        left_click.connect_pressed(move |_, clicks, x, y| {
+        //Left clicks are never claimed since that would stop the GestureDrag from dragging. Single
+        //clicks are handled in drag_begin so that the object they go to is the one pressed.
+        left_click.connect_pressed(move |gesture, clicks, x, y| {
             if clicks == 2 {
-                my_click(ClickType::Double, x, y);
+                my_click(ClickType::Double, gesture, clicks, x, y);
             }
         });
         self.obj().add_controller(left_click);
         let middle_click = GestureClick::new();
         middle_click.set_button(2);
         let my_click = click.clone();
-        middle_click.connect_pressed(move |_, clicks, x, y| {
-            if clicks == 1 {
-                my_click(ClickType::Middle, x, y);
+        middle_click.connect_pressed(move |gesture, clicks, x, y| {
+            if clicks == 1 && my_click(ClickType::Middle, gesture, clicks, x, y).is_some() {
+                gesture.set_state(EventSequenceState::Claimed);
             }
         });
         self.obj().add_controller(middle_click);
         let right_click = GestureClick::new();
         right_click.set_button(3);
-        right_click.connect_pressed(move |_, clicks, x, y| {
-            if clicks == 1 {
-                click(ClickType::Right, x, y);
+        right_click.connect_pressed(move |gesture, clicks, x, y| {
+            if clicks == 1 && click(ClickType::Right, gesture, clicks, x, y).is_some() {
+                gesture.set_state(EventSequenceState::Claimed);
             }
         });
         self.obj().add_controller(right_click);
     }
 }
 impl WidgetImpl for DragArea {}
-impl DrawingAreaImpl for DragArea {}
+impl ScrollableImpl for DragArea {}
+impl DrawingAreaImpl for DragArea {
+    fn resize(&self, width: i32, height: i32) {
+        self.parent_resize(width, height);
+        self.update_adjustments();
+        if self.keep_inside.get() {
+            self.push_inside();
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    struct Square;
+    impl Draggable for Square {
+        fn draw(
+            &self,
+            _context: &Context,
+            _x: f64,
+            _y: f64,
+            _state: DrawState,
+        ) -> Result<(), Error> {
+            Ok(())
+        }
+        fn get_limits(&self) -> (f64, f64, f64, f64) {
+            (5.0, 5.0, 5.0, 5.0)
+        }
+    }
+    fn push(holder: &mut DraggableSetHolder, layer: &str, x: f64, y: f64) -> ItemId {
+        holder.push(layer, (Box::new(Square) as Box<dyn Draggable>).into(), x, y)
+    }
+    fn ids<'a>(iter: impl Iterator<Item = DraggableBorrowAndCoordinates<'a>>) -> Vec<ItemId> {
+        iter.map(|draggable_and_coords| draggable_and_coords.id)
+            .collect()
+    }
+    #[test]
+    fn iterates_empty_holder_both_ways() {
+        let holder = DraggableSetHolder::new();
+        assert!(ids(holder.iter()).is_empty());
+        assert!(ids(holder.iter().rev()).is_empty());
+        assert!(ids(holder.iter_visible().rev()).is_empty());
+    }
+    #[test]
+    fn iterates_backward() {
+        let mut holder = DraggableSetHolder::new();
+        let a = push(&mut holder, DEFAULT_LAYER, 0.0, 0.0);
+        let b = push(&mut holder, DEFAULT_LAYER, 0.0, 0.0);
+        let c = push(&mut holder, DEFAULT_LAYER, 0.0, 0.0);
+        assert_eq!(ids(holder.iter()), vec![a, b, c]);
+        assert_eq!(ids(holder.iter().rev()), vec![c, b, a]);
+        let mut iter = holder.iter();
+        assert_eq!(iter.next_back().map(|item| item.id), Some(c));
+        assert_eq!(iter.next().map(|item| item.id), Some(a));
+        assert_eq!(iter.next_back().map(|item| item.id), Some(b));
+        assert!(iter.next_back().is_none());
+        assert!(iter.next().is_none());
+    }
+    #[test]
+    fn marquee_selects_objects_inside() {
+        let mut holder = DraggableSetHolder::new();
+        let inside = push(&mut holder, DEFAULT_LAYER, 10.0, 10.0);
+        let partly_inside = push(&mut holder, DEFAULT_LAYER, 28.0, 10.0);
+        let outside = push(&mut holder, DEFAULT_LAYER, 100.0, 100.0);
+        let locked = push(&mut holder, "locked", 20.0, 20.0);
+        holder.layer_mut("locked").unwrap().locked = true;
+        assert_eq!(holder.in_rect(0.0, 0.0, 30.0, 30.0), vec![locked, inside]);
+        assert_eq!(
+            holder.marquee_selection(&[], 0.0, 0.0, 30.0, 30.0),
+            vec![inside]
+        );
+        assert_eq!(
+            holder.marquee_selection(&[outside], 0.0, 0.0, 40.0, 30.0),
+            vec![outside, partly_inside, inside]
+        );
+        assert!(holder.in_rect(50.0, 50.0, 60.0, 60.0).is_empty());
+        assert!(DraggableSetHolder::new()
+            .marquee_selection(&[], 0.0, 0.0, 30.0, 30.0)
+            .is_empty());
+    }
+    #[test]
+    fn iterates_visible_backward() {
+        let mut holder = DraggableSetHolder::new();
+        let a = push(&mut holder, DEFAULT_LAYER, 0.0, 0.0);
+        push(&mut holder, "hidden", 0.0, 0.0);
+        let c = push(&mut holder, DEFAULT_LAYER, 0.0, 0.0);
+        holder.layer_mut("hidden").unwrap().visible = false;
+        assert_eq!(ids(holder.iter_visible().rev()), vec![c, a]);
+    }
+}
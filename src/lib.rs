@@ -5,14 +5,301 @@
 #![warn(missing_docs)]
 use cairo::{Context, Error};
 use glib::Object;
-use gtk4::{cairo, glib, prelude::*, subclass::prelude::*, DrawingArea};
+use gtk4::{cairo, gdk, glib, prelude::*, subclass::prelude::*, DrawingArea};
 use std::cell::RefCell;
 use std::rc::Rc;
+mod guides;
 mod imp;
+///The name of the layer that objects are added to by [`DragArea::push_box`],
+///[`DragArea::push_rc`], and [`DragArea::push_rc_ref_cell`]. Every `DragArea` starts with only
+///this layer, and it cannot be removed.
+pub const DEFAULT_LAYER: &str = "default";
+///A handle to an object in a [`DragArea`], returned when the object is added. It keeps referring
+///to the same object when objects are reordered or removed and is never reused for another object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ItemId(u64);
+///A coordinate system for points given to a [`DragArea`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coordinates {
+    ///Relative to the top left corner of the `DragArea` widget, like the coordinates of pointer
+    ///events.
+    Widget,
+    ///The coordinates objects are placed at, as used by [`DragArea::set_position`]. These differ
+    ///from widget coordinates by the translation from scrolling and the scale from zooming.
+    Scene,
+}
+///A rectangle given by the coordinates of one corner and its size.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rectangle {
+    ///The x coordinate of the corner.
+    pub x: f64,
+    ///The y coordinate of the corner.
+    pub y: f64,
+    ///The width of the rectangle. This may be negative, in which case the rectangle extends to the
+    ///left of the corner.
+    pub width: f64,
+    ///The height of the rectangle. This may be negative, in which case the rectangle extends above
+    ///the corner.
+    pub height: f64,
+}
+impl Rectangle {
+    ///Constructs a `Rectangle`.
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+///When dragged objects snap to a [`Grid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapMode {
+    ///Objects jump between grid points as they are dragged.
+    Continuous,
+    ///Objects follow the pointer freely and snap to the nearest grid point when released.
+    OnRelease,
+}
+///A grid that dragged objects snap to, set with [`DragArea::set_grid`]. The coordinates of an
+///object, those passed to [`Draggable::draw`], are what is snapped to the grid. When several
+///selected objects are dragged together, the one under the pointer is snapped and the others keep
+///their positions relative to it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grid {
+    ///The horizontal distance between grid points.
+    pub cell_width: f64,
+    ///The vertical distance between grid points.
+    pub cell_height: f64,
+    ///The x coordinate of a grid point. This offsets the grid horizontally.
+    pub origin_x: f64,
+    ///The y coordinate of a grid point. This offsets the grid vertically.
+    pub origin_y: f64,
+    ///Whether objects snap horizontally.
+    pub snap_x: bool,
+    ///Whether objects snap vertically.
+    pub snap_y: bool,
+    ///When objects snap.
+    pub mode: SnapMode,
+    ///Modifier keys that disable snapping while any of them are held.
+    pub bypass_modifiers: gdk::ModifierType,
+}
+impl Grid {
+    ///Constructs a `Grid` with the given cell size and a grid point at the origin. Objects snap
+    ///continuously on both axes, and holding Alt disables snapping.
+    pub fn new(cell_width: f64, cell_height: f64) -> Self {
+        Self {
+            cell_width,
+            cell_height,
+            origin_x: 0.0,
+            origin_y: 0.0,
+            snap_x: true,
+            snap_y: true,
+            mode: SnapMode::Continuous,
+            bypass_modifiers: gdk::ModifierType::ALT_MASK,
+        }
+    }
+    ///Returns the nearest grid point to the given coordinates on the axes that snap.
+    pub fn snap(&self, x: f64, y: f64) -> (f64, f64) {
+        let x = if self.snap_x && self.cell_width > 0.0 {
+            ((x - self.origin_x) / self.cell_width).round() * self.cell_width + self.origin_x
+        } else {
+            x
+        };
+        let y = if self.snap_y && self.cell_height > 0.0 {
+            ((y - self.origin_y) / self.cell_height).round() * self.cell_height + self.origin_y
+        } else {
+            y
+        };
+        (x, y)
+    }
+}
+///Settings for aligning dragged objects with others, set with [`DragArea::set_alignment_guides`].
+///When an edge or the center of the dragged object comes within `threshold` of an edge or the
+///center of another object according to [`Draggable::get_limits`], it snaps into alignment and a
+///guide line is drawn. When several selected objects are dragged together, the one under the
+///pointer is aligned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlignmentGuides {
+    ///How close, in the coordinates objects are placed at, an edge must be to snap.
+    pub threshold: f64,
+    ///Whether to also snap the dragged object to where the gaps between it and its neighbors in
+    ///the same row or column are equal to each other or to the gap between the next two objects,
+    ///drawing dashed lines across the equal gaps.
+    pub equal_spacing: bool,
+    ///Modifier keys that disable alignment while any of them are held.
+    pub bypass_modifiers: gdk::ModifierType,
+}
+impl AlignmentGuides {
+    ///Constructs `AlignmentGuides` with the given threshold and equal spacing enabled. Holding Alt
+    ///disables alignment.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            equal_spacing: true,
+            bypass_modifiers: gdk::ModifierType::ALT_MASK,
+        }
+    }
+}
+///Settings for the `DragArea` scrolling on after a fast pan and objects moving on after being
+///thrown, set with [`DragArea::set_inertia`]. Thrown objects slow down and stop at the bounds of
+///the `DragArea` and follow their constraints. If a [`Grid`] is set and was not bypassed when they
+///were released, they snap to it once they stop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Inertia {
+    ///How quickly the motion slows down. The velocity is multiplied by e^(-`friction`) every
+    ///second, so higher values stop sooner. This must be greater than 0.
+    pub friction: f64,
+    ///Whether the `DragArea` scrolls on after it is panned.
+    pub panning: bool,
+    ///Whether objects move on after they are thrown.
+    pub throwing: bool,
+}
+impl Inertia {
+    ///Constructs `Inertia` with the given friction for both panning and throwing.
+    pub fn new(friction: f64) -> Self {
+        Self {
+            friction,
+            panning: true,
+            throwing: true,
+        }
+    }
+}
+///A restriction on where an object can be dragged or moved with the arrow keys, set with
+///[`DragArea::set_constraint`]. It applies to the object's coordinates, those passed to
+///[`Draggable::draw`], and is applied before [`Draggable::constrain`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Constraint {
+    ///The object only moves horizontally.
+    Horizontal,
+    ///The object only moves vertically.
+    Vertical,
+    ///The object moves along the line segment between two points, e.g., for a slider.
+    Line {
+        ///The x coordinate of one end of the segment.
+        x0: f64,
+        ///The y coordinate of one end of the segment.
+        y0: f64,
+        ///The x coordinate of the other end of the segment.
+        x1: f64,
+        ///The y coordinate of the other end of the segment.
+        y1: f64,
+    },
+    ///The object moves along a circle, e.g., for a knob.
+    Circle {
+        ///The x coordinate of the center of the circle.
+        center_x: f64,
+        ///The y coordinate of the center of the circle.
+        center_y: f64,
+        ///The radius of the circle.
+        radius: f64,
+    },
+    ///The object stays inside a rectangle.
+    Rectangle(Rectangle),
+}
+impl Constraint {
+    ///Given the coordinates an object had when it started moving and the coordinates it would
+    ///move to, returns the nearest coordinates it is allowed to move to.
+    pub fn constrain(&self, start_x: f64, start_y: f64, x: f64, y: f64) -> (f64, f64) {
+        match *self {
+            Self::Horizontal => (x, start_y),
+            Self::Vertical => (start_x, y),
+            Self::Line { x0, y0, x1, y1 } => {
+                let (line_x, line_y) = (x1 - x0, y1 - y0);
+                let length_squared = line_x * line_x + line_y * line_y;
+                if length_squared == 0.0 {
+                    return (x0, y0);
+                }
+                let t = (((x - x0) * line_x + (y - y0) * line_y) / length_squared).clamp(0.0, 1.0);
+                (x0 + t * line_x, y0 + t * line_y)
+            }
+            Self::Circle {
+                center_x,
+                center_y,
+                radius,
+            } => {
+                let (offset_x, offset_y) = (x - center_x, y - center_y);
+                let distance = offset_x.hypot(offset_y);
+                if distance == 0.0 {
+                    //Every point on the circle is equally close, so stay where it started.
+                    return (start_x, start_y);
+                }
+                (
+                    center_x + offset_x / distance * radius,
+                    center_y + offset_y / distance * radius,
+                )
+            }
+            Self::Rectangle(rect) => {
+                let (left, right) = (
+                    rect.x.min(rect.x + rect.width),
+                    rect.x.max(rect.x + rect.width),
+                );
+                let (top, bottom) = (
+                    rect.y.min(rect.y + rect.height),
+                    rect.y.max(rect.y + rect.height),
+                );
+                (x.clamp(left, right), y.clamp(top, bottom))
+            }
+        }
+    }
+}
+///How far a scrollable [`DragArea`] can be scrolled, set with [`DragArea::set_scroll_limits`].
+///The `DragArea` can be scrolled as long as the part of the scene it shows stays inside the
+///limits or, if the `DragArea` is larger than the limits, as long as all of the limits stay in
+///view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollLimits {
+    ///The smallest rectangle containing every visible object according to
+    ///[`Draggable::get_limits`], extended by `margin` on every side. There are no limits when
+    ///there are no visible objects.
+    Content {
+        ///How far past the objects the `DragArea` can be scrolled.
+        margin: f64,
+    },
+    ///A rectangle in scene coordinates (see [`Coordinates::Scene`]).
+    Rectangle(Rectangle),
+}
+///Information about an object's state in its [`DragArea`] that may affect how it is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DrawState {
+    ///Whether the object is selected.
+    pub selected: bool,
+    ///Whether the object has keyboard focus. The `DragArea` draws a focus rectangle around the
+    ///object's limits in this case, so drawing anything else is optional.
+    pub focused: bool,
+    ///Whether the object is the topmost one under the pointer according to
+    ///[`Draggable::contains`].
+    pub hovered: bool,
+}
+///Information about a pointer event on an object, passed to the click methods of [`Draggable`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct PointerEvent {
+    ///The x coordinate of the event relative to the object, with the object's last draw at the
+    ///origin as in [`Draggable::contains`].
+    pub x: f64,
+    ///The y coordinate of the event relative to the object.
+    pub y: f64,
+    ///The x coordinate of the event in widget coordinates (see [`Coordinates::Widget`]).
+    pub widget_x: f64,
+    ///The y coordinate of the event in widget coordinates.
+    pub widget_y: f64,
+    ///The mouse button, where 1 is left, 2 is middle, and 3 is right. Touches are reported as 1.
+    pub button: u32,
+    ///How many presses in a row there have been, e.g., 2 for a double click.
+    pub click_count: i32,
+    ///The modifier keys and buttons held during the event.
+    pub modifiers: gdk::ModifierType,
+    ///The kind of device that caused the event, if known.
+    pub device: Option<gdk::InputSource>,
+}
 ///An object that is rendered on a Cairo Context and can be dragged.
 pub trait Draggable {
-    ///Draws the object on a Cairo Context.
-    fn draw(&self, context: &Context, x: f64, y: f64) -> Result<(), Error>;
+    ///Draws the object on a Cairo Context. `state` can be used to, e.g., highlight the object when
+    ///it is selected. `x` and `y` are the object's coordinates in the scene (see
+    ///[`Coordinates::Scene`]), and the context is transformed for scrolling and zooming.
+    fn draw(&self, context: &Context, x: f64, y: f64, state: DrawState) -> Result<(), Error>;
     ///Returns how far the object extends from the coordinates given in `draw` as a tuple of
     ///`(-x, +x, -y, +y)`. These should be positive in all directions, e.g., a centered circle with
     ///a radius of 50 should return `(50.0, 50.0, 50.0, 50.0)`.
@@ -31,30 +318,178 @@ pub trait Draggable {
     fn can_scroll(&self, x: f64, y: f64) -> bool {
         !self.contains(x, y)
     }
+    ///Given relative coordinates with the object's last draw at the origin, returns the cursor to
+    ///show while the pointer is over that point of the object, e.g., one made with
+    ///[`gdk::Cursor::from_name`]. This is only called for points where
+    ///[`contains`](Self::contains) returns true. The default implementation returns `None`, which
+    ///shows the "grab" cursor. The "grabbing" cursor is always shown while the object is dragged.
+    fn cursor(&self, x: f64, y: f64) -> Option<gdk::Cursor> {
+        let _ = (x, y);
+        None
+    }
     ///Returns whether to keep or remove the object. If this returns false, the object will be
     ///removed from its [`DragArea`]; otherwise, nothing will change and it will still be drawn. It
     ///is important to note that this method will not be called outside of the [`DragArea`] drawing
     ///function, and to trigger removal of the object, the drawing function must be called *and*
-    ///this must return false during that call.
+    ///this must return false during that call. To remove an object immediately, use
+    ///[`DragArea::remove`] instead.
     fn retain(&self) -> bool {
         true
     }
+    ///Given the coordinates the object is about to be dragged or nudged to, returns where it
+    ///should actually go, or `None` to keep it where it is. Snapping, alignment guides,
+    ///constraints, and keeping the object inside the [`DragArea`] are applied before this is
+    ///called, and the result is kept inside the [`DragArea`] again afterward. This is not called
+    ///for positions set with [`DragArea::set_position`] or [`DragArea::move_by`]. The default
+    ///implementation allows any position.
+    fn can_move_to(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        Some((x, y))
+    }
+    ///Given the coordinates the object is about to be dragged or nudged to, returns the nearest
+    ///coordinates it can move to, e.g., to keep it on a custom path. This is applied after its
+    ///[`Constraint`], if any, and before [`can_move_to`](Self::can_move_to). The default
+    ///implementation allows any position.
+    fn constrain(&self, x: f64, y: f64) -> (f64, f64) {
+        (x, y)
+    }
+    ///Run when the object starts being dragged, including when it is dragged as part of a
+    ///selection.
+    fn on_drag_begin(&self) {}
+    ///Run each time the object is moved during a drag with the coordinates it was moved to.
+    fn on_drag_motion(&self, x: f64, y: f64) {
+        let _ = (x, y);
+    }
+    ///Run when a drag of the object ends with the coordinates it was at when the drag began and
+    ///the coordinates it ended up at, e.g., to save its new position. This is run after
+    ///[`on_drop`](Self::on_drop). If GTK cancels the drag, e.g., because another widget claimed the
+    ///touch, the object is moved back to where the drag began and this is run with `to` equal to
+    ///`from`.
+    fn on_drag_end(&self, from: (f64, f64), to: (f64, f64)) {
+        let _ = (from, to);
+    }
+    ///Returns whether `other` may be dropped onto this object. When an object is dragged, the
+    ///topmost object under the pointer for which this returns true becomes its drop target. If
+    ///several selected objects are dragged together, `other` is the one that was pressed. The
+    ///default implementation accepts nothing.
+    fn accepts_drop(&self, other: &dyn Draggable) -> bool {
+        let _ = other;
+        false
+    }
+    ///Run each time `other` is dragged while this object is its drop target (see
+    ///[`accepts_drop`](Self::accepts_drop)), e.g., to show that it can be dropped here.
+    fn on_drag_over(&self, other: &dyn Draggable) {
+        let _ = other;
+    }
+    ///Run when this object stops being the drop target of `other` without it being dropped.
+    fn on_drag_leave(&self, other: &dyn Draggable) {
+        let _ = other;
+    }
+    ///Run when `other` is released while this object is its drop target. This is run instead of
+    ///[`on_drag_leave`](Self::on_drag_leave).
+    fn on_drop(&self, other: &dyn Draggable) {
+        let _ = other;
+    }
+    ///Run when Enter is pressed while the object is selected or, if nothing is selected, focused.
+    fn on_activate(&self) {}
+    ///Run when the object becomes the topmost one under the pointer according to
+    ///[`contains`](Self::contains).
+    fn on_pointer_enter(&self) {}
+    ///Run when the object stops being the topmost one under the pointer.
+    fn on_pointer_leave(&self) {}
+    ///Run each time the pointer moves while the object is the topmost one under it. Like
+    ///[`contains`](Self::contains), this is given relative coordinates with the object's last draw
+    ///at the origin.
+    fn on_pointer_motion(&self, x: f64, y: f64) {
+        let _ = (x, y);
+    }
+    ///Run after the object has been removed from its [`DragArea`], either because
+    ///[`retain`](Self::retain) returned false or because of [`DragArea::remove`],
+    ///[`DragArea::remove_where`], or [`DragArea::clear`].
+    fn on_remove(&self) {}
+    ///Run when a point for which [`contains`](Self::contains) returns true is left single clicked.
+    ///This is run when the click is pressed, not released, so it is also run when the object
+    ///starts being dragged.
+    ///
+    ///Clicks go to the topmost object under the pointer first. Returning
+    ///[`Propagation::Proceed`](glib::Propagation::Proceed) from this or any other click method
+    ///passes the click on to the next object beneath, and if no object returns
+    ///[`Propagation::Stop`](glib::Propagation::Stop), middle and right clicks are left for the
+    ///[`DragArea`]'s parent widgets to handle. The default implementations return
+    ///`Propagation::Stop`.
+    ///
+    ///For left clicks, the object that returns `Propagation::Stop` from this method is the one
+    ///that is pressed, so it is the one that is selected and dragged. If every object under the
+    ///pointer returns `Propagation::Proceed`, the press goes to the [`DragArea`] as if there were
+    ///no object there, e.g., drawing a selection rectangle or emitting `background-clicked`. The
+    ///second press of a double click goes to the same object as the first without running this.
+    fn on_click(&self, event: &PointerEvent) -> glib::Propagation {
+        let _ = event;
+        glib::Propagation::Stop
+    }
     ///Run when a point for which [`contains`](Self::contains) returns true is left double clicked.
     ///This is run when the click is pressed, not released.
-    fn on_double_click(&self) {}
-    ///Run when a point for which [`contains`](Self::contains) returns true is right single clicked.
-    ///This is run when the click is pressed, not released.
-    fn on_middle_click(&self) {}
+    fn on_double_click(&self, event: &PointerEvent) -> glib::Propagation {
+        let _ = event;
+        glib::Propagation::Stop
+    }
     ///Run when a point for which [`contains`](Self::contains) returns true is middle single clicked.
     ///This is run when the click is pressed, not released.
-    fn on_right_click(&self) {}
+    fn on_middle_click(&self, event: &PointerEvent) -> glib::Propagation {
+        let _ = event;
+        glib::Propagation::Stop
+    }
+    ///Run when a point for which [`contains`](Self::contains) returns true is right single clicked.
+    ///This is run when the click is pressed, not released.
+    fn on_right_click(&self, event: &PointerEvent) -> glib::Propagation {
+        let _ = event;
+        glib::Propagation::Stop
+    }
 }
 glib::wrapper! {
     ///A subclass of [`gtk4::DrawingArea`] allowing for drag-and-drop of objects implementing the
     ///[`Draggable`] trait. Can optionally be scrolled by dragging in an area without an object.
+    ///
+    ///Pressing an object selects it, and holding Shift or Control while doing so adds it to or
+    ///removes it from the selection instead. Dragging a selected object moves every selected
+    ///object with it. Dragging where there is no object and the `DragArea` cannot be scrolled
+    ///selects the objects within the dragged rectangle.
+    ///
+    ///On a touchscreen, several fingers can drag objects at once. While one finger is dragging, a
+    ///finger pressing another object drags only that object, adding it to the selection, and
+    ///fingers pressing where there is no object are ignored.
+    ///
+    ///The `DragArea` can also be used with the keyboard. Tab and Shift+Tab move focus between
+    ///objects, selecting them, and the arrow keys move the selected objects or, if nothing is
+    ///selected, the focused object (see [`set_nudge_step`](Self::set_nudge_step)). Delete and
+    ///Backspace remove them, and Enter activates them (see [`Draggable::on_activate`]).
+    ///
+    ///A scrollable `DragArea` implements [`gtk4::Scrollable`], so it can be put in a
+    ///[`gtk4::ScrolledWindow`] to get scrollbars. Their range covers every visible object or the
+    ///scroll limits (see [`set_scroll_limits`](Self::set_scroll_limits)), if any, along with
+    ///the part of the scene currently shown. Dragging to scroll keeps working alongside them, and
+    ///the `ScrolledWindow` handles scrolling with the mouse wheel.
+    ///
+    ///A non-scrollable `DragArea` also implements `Scrollable`, so a `ScrolledWindow` does not wrap
+    ///it in a [`gtk4::Viewport`] automatically, but it ignores the adjustments. To scroll a
+    ///non-scrollable `DragArea` in a `ScrolledWindow`, put it in a `Viewport` first.
+    ///
+    ///# Signals
+    ///Coordinates are passed to signals in scene coordinates, and signals about an object also
+    ///pass its [`ItemId`] as a `u64`. The `connect_*` methods take care of converting it.
+    ///- `item-drag-begin(id: u64, x: f64, y: f64)`: an object started being dragged
+    ///- `item-moved(id: u64, x: f64, y: f64)`: a drag, an arrow key, or resizing moved an object
+    ///- `item-activated(id: u64, x: f64, y: f64)`: an object was double clicked or activated
+    ///  with Enter
+    ///- `item-removed(id: u64, x: f64, y: f64)`: an object was removed
+    ///- `selection-changed()`: objects were selected or deselected (see
+    ///  [`selection`](Self::selection))
+    ///- `background-clicked(x: f64, y: f64)`: a point with no object was clicked without dragging
+    ///- `scroll-changed(x: f64, y: f64)`: the `DragArea` was scrolled to the given location (see
+    ///  [`get_scroll_location`](Self::get_scroll_location))
+    ///- `zoom-changed(zoom: f64)`: the `DragArea` was zoomed (see [`zoom`](Self::zoom))
     pub struct DragArea(ObjectSubclass<imp::DragArea>)
         @extends DrawingArea, gtk4::Widget,
-        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget;
+        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget, gtk4::Scrollable;
 }
 impl DragArea {
     ///Constructs a non-scrollable `DragArea`.
@@ -71,30 +506,438 @@ impl DragArea {
         output_imp.set_scrollable(true);
         output
     }
-    ///Adds a draggable object contained in a `Box` to the `DragArea`.
-    pub fn push_box(&self, item: Box<impl Draggable + 'static>, x: f64, y: f64) {
+    ///Adds a draggable object contained in a `Box` to the `DragArea`, returning its [`ItemId`].
+    pub fn push_box(&self, item: Box<impl Draggable + 'static>, x: f64, y: f64) -> ItemId {
+        let self_imp = imp::DragArea::from_obj(self);
+        let id = self_imp.push_box(DEFAULT_LAYER, item, x, y);
+        self.queue_draw();
+        id
+    }
+    ///Adds a draggable object contained in a `Box` to the given layer of the `DragArea`,
+    ///returning its [`ItemId`]. The layer is added on top of the others if it does not exist.
+    pub fn push_box_to_layer(
+        &self,
+        layer: &str,
+        item: Box<impl Draggable + 'static>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
+        let self_imp = imp::DragArea::from_obj(self);
+        let id = self_imp.push_box(layer, item, x, y);
+        self.queue_draw();
+        id
+    }
+    ///Adds a draggable object contained in an `Rc` to the `DragArea`, returning its [`ItemId`].
+    pub fn push_rc(&self, item: Rc<impl Draggable + 'static>, x: f64, y: f64) -> ItemId {
+        let self_imp = imp::DragArea::from_obj(self);
+        let id = self_imp.push_rc(DEFAULT_LAYER, item, x, y);
+        self.queue_draw();
+        id
+    }
+    ///Adds a draggable object contained in an `Rc` to the given layer of the `DragArea`,
+    ///returning its [`ItemId`]. The layer is added on top of the others if it does not exist.
+    pub fn push_rc_to_layer(
+        &self,
+        layer: &str,
+        item: Rc<impl Draggable + 'static>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
         let self_imp = imp::DragArea::from_obj(self);
-        self_imp.push_box(item, x, y);
+        let id = self_imp.push_rc(layer, item, x, y);
         self.queue_draw();
+        id
     }
-    ///Adds a draggable object contained in an `Rc` to the `DragArea`.
-    pub fn push_rc(&self, item: Rc<impl Draggable + 'static>, x: f64, y: f64) {
+    ///Adds a draggable object contained in an `Rc<RefCell>` to the `DragArea`, returning its
+    ///[`ItemId`].
+    pub fn push_rc_ref_cell(
+        &self,
+        item: Rc<RefCell<impl Draggable + 'static>>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
         let self_imp = imp::DragArea::from_obj(self);
-        self_imp.push_rc(item, x, y);
+        let id = self_imp.push_rc_ref_cell(DEFAULT_LAYER, item, x, y);
         self.queue_draw();
+        id
     }
-    ///Adds a draggable object contained in an `Rc<RefCell>` to the `DragArea`.
-    pub fn push_rc_ref_cell(&self, item: Rc<RefCell<impl Draggable + 'static>>, x: f64, y: f64) {
+    ///Adds a draggable object contained in an `Rc<RefCell>` to the given layer of the `DragArea`,
+    ///returning its [`ItemId`]. The layer is added on top of the others if it does not exist.
+    pub fn push_rc_ref_cell_to_layer(
+        &self,
+        layer: &str,
+        item: Rc<RefCell<impl Draggable + 'static>>,
+        x: f64,
+        y: f64,
+    ) -> ItemId {
         let self_imp = imp::DragArea::from_obj(self);
-        self_imp.push_rc_ref_cell(item, x, y);
+        let id = self_imp.push_rc_ref_cell(layer, item, x, y);
         self.queue_draw();
+        id
+    }
+    ///Removes an object from the `DragArea` immediately, returning whether it was present.
+    pub fn remove(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let removed = self_imp.remove(id);
+        self.queue_draw();
+        removed
+    }
+    ///Removes every object for which `predicate` returns true from the `DragArea` immediately.
+    ///`predicate` is run on every object before any are removed, so it can use the `DragArea`.
+    pub fn remove_where(&self, predicate: impl FnMut(ItemId, &dyn Draggable) -> bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.remove_where(predicate);
+        self.queue_draw();
+    }
+    ///Removes every object from the `DragArea` immediately.
+    pub fn clear(&self) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.clear();
+        self.queue_draw();
+    }
+    ///Gets the coordinates of an object, or `None` if it is not in the `DragArea`. These are the
+    ///coordinates passed to [`Draggable::draw`] before any translation from scrolling.
+    pub fn position(&self, id: ItemId) -> Option<(f64, f64)> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.position(id)
+    }
+    ///Moves an object to the given coordinates, returning whether it is in the `DragArea`. If the
+    ///`DragArea` is not scrollable, the object is kept inside it just as it is when dragged.
+    pub fn set_position(&self, id: ItemId, x: f64, y: f64) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.set_position(id, x, y);
+        self.queue_draw();
+        found
+    }
+    ///Moves an object relative to its current coordinates, returning whether it is in the
+    ///`DragArea`. This is subject to the same limits as [`set_position`](Self::set_position).
+    pub fn move_by(&self, id: ItemId, dx: f64, dy: f64) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.move_by(id, dx, dy);
+        self.queue_draw();
+        found
+    }
+    ///Restricts where an object can be dragged or moved with the arrow keys or, if `constraint` is
+    ///`None`, removes its restriction, returning whether the object is in the `DragArea`. This
+    ///does not move the object if it is already somewhere the constraint would not allow.
+    pub fn set_constraint(&self, id: ItemId, constraint: Option<Constraint>) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_constraint(id, constraint)
+    }
+    ///Gets the [`Constraint`] on an object, if any.
+    pub fn constraint(&self, id: ItemId) -> Option<Constraint> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.constraint(id)
+    }
+    ///Sets whether holding Shift while dragging keeps the dragged objects on whichever axis the
+    ///pointer has moved farther along. This is enabled by default.
+    pub fn set_shift_axis_lock(&self, shift_axis_lock: bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_shift_axis_lock(shift_axis_lock);
+    }
+    ///Gets the stacking position of an object within its layer, or `None` if it is not in the
+    ///`DragArea`. Objects in a layer are drawn in order of their z-index, so 0 is the bottom.
+    pub fn z_index(&self, id: ItemId) -> Option<usize> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.z_index(id)
+    }
+    ///Moves an object to the given stacking position within its layer, shifting the objects
+    ///between its old and new positions. Indices past the top place the object on top. Returns
+    ///whether the object is in the `DragArea`.
+    pub fn set_z_index(&self, id: ItemId, z_index: usize) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.set_z_index(id, z_index);
+        self.queue_draw();
+        found
+    }
+    ///Moves an object up one place in its layer's stacking order, returning whether it is in the
+    ///`DragArea`.
+    pub fn raise(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.raise(id);
+        self.queue_draw();
+        found
+    }
+    ///Moves an object down one place in its layer's stacking order, returning whether it is in the
+    ///`DragArea`.
+    pub fn lower(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.lower(id);
+        self.queue_draw();
+        found
+    }
+    ///Moves an object above all others in its layer, returning whether it is in the `DragArea`.
+    pub fn bring_to_front(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.bring_to_front(id);
+        self.queue_draw();
+        found
+    }
+    ///Moves an object below all others in its layer, returning whether it is in the `DragArea`.
+    pub fn send_to_back(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.send_to_back(id);
+        self.queue_draw();
+        found
+    }
+    ///Sets a rectangle in scene coordinates (see [`Coordinates::Scene`]) that objects are kept
+    ///inside of according to [`Draggable::get_limits`] or, if `bounds` is `None`, goes back to
+    ///keeping them inside the allocated size of a non-scrollable `DragArea` and not restricting
+    ///them in a scrollable one.
+    pub fn set_bounds(&self, bounds: Option<Rectangle>) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_bounds(bounds);
+    }
+    ///Gets the rectangle objects are kept inside of, if one was set with
+    ///[`set_bounds`](Self::set_bounds).
+    pub fn bounds(&self) -> Option<Rectangle> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.bounds()
+    }
+    ///Sets whether objects are moved back inside the `DragArea` or its bounds (see
+    ///[`set_bounds`](Self::set_bounds)) when it is resized or the bounds change. Otherwise,
+    ///objects left outside stay there until they are moved. This is disabled by default.
+    pub fn set_keep_inside(&self, keep_inside: bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_keep_inside(keep_inside);
+    }
+    ///Sets whether an object is moved above all others in its layer when it is pressed to be
+    ///dragged. This is enabled by default.
+    pub fn set_raise_on_press(&self, raise_on_press: bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_raise_on_press(raise_on_press);
+    }
+    ///Gets the selected objects in the order they were selected.
+    pub fn selection(&self) -> Vec<ItemId> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.selection()
+    }
+    ///Returns whether an object is selected.
+    pub fn is_selected(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.is_selected(id)
+    }
+    ///Adds an object to the selection, returning whether it is in the `DragArea`.
+    pub fn select(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.select(id);
+        self.queue_draw();
+        found
+    }
+    ///Removes an object from the selection, returning whether it was selected.
+    pub fn deselect(&self, id: ItemId) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let was_selected = self_imp.deselect(id);
+        self.queue_draw();
+        was_selected
+    }
+    ///Replaces the selection with the given objects. Objects not in the `DragArea` are skipped.
+    pub fn set_selection(&self, ids: impl IntoIterator<Item = ItemId>) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_selection(ids);
+        self.queue_draw();
+    }
+    ///Deselects every object.
+    pub fn clear_selection(&self) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.clear_selection();
+        self.queue_draw();
+    }
+    ///Gets the object with keyboard focus, if any.
+    pub fn focused(&self) -> Option<ItemId> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.focused()
+    }
+    ///Gives an object keyboard focus or, if `id` is `None`, clears it. Returns false if the object
+    ///is not in the `DragArea`.
+    pub fn set_focused(&self, id: Option<ItemId>) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.set_focused(id);
+        self.queue_draw();
+        found
+    }
+    ///Sets how far the arrow keys move objects, without and with Shift held. The defaults are 1
+    ///and 10.
+    pub fn set_nudge_step(&self, step: f64, large_step: f64) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_nudge_step(step, large_step);
+    }
+    ///Sets the grid that dragged objects snap to or, if `grid` is `None`, disables snapping.
+    ///Snapping happens before objects are kept inside a non-scrollable `DragArea`, so they may be
+    ///moved off of the grid at its edges.
+    pub fn set_grid(&self, grid: Option<Grid>) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_grid(grid);
+    }
+    ///Gets the grid that dragged objects snap to, if any.
+    pub fn grid(&self) -> Option<Grid> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.grid()
+    }
+    ///Sets how dragged objects are aligned with others or, if `alignment_guides` is `None`,
+    ///disables alignment. Alignment happens after snapping to the [`Grid`], if any, and before
+    ///objects are kept inside a non-scrollable `DragArea`.
+    pub fn set_alignment_guides(&self, alignment_guides: Option<AlignmentGuides>) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_alignment_guides(alignment_guides);
+    }
+    ///Gets how dragged objects are aligned with others, if they are.
+    pub fn alignment_guides(&self) -> Option<AlignmentGuides> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.alignment_guides()
+    }
+    ///Adds an empty layer on top of the others, returning false if a layer with the name already
+    ///exists.
+    pub fn add_layer(&self, name: &str) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.add_layer(name)
+    }
+    ///Removes a layer and every object on it, returning whether it was removed. The
+    ///[`DEFAULT_LAYER`] cannot be removed.
+    pub fn remove_layer(&self, name: &str) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let removed = self_imp.remove_layer(name);
+        self.queue_draw();
+        removed
+    }
+    ///Gets the names of all layers from bottom to top.
+    pub fn layers(&self) -> Vec<String> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.layers()
+    }
+    ///Sets whether the objects on a layer are drawn, returning whether the layer exists. Objects on
+    ///hidden layers cannot be dragged or clicked.
+    pub fn set_layer_visible(&self, name: &str, visible: bool) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.set_layer_visible(name, visible);
+        self.queue_draw();
+        found
+    }
+    ///Sets whether the objects on a layer are locked, returning whether the layer exists. Objects
+    ///on locked layers are still drawn but cannot be dragged or clicked, and the `DragArea` can be
+    ///scrolled by dragging them.
+    pub fn set_layer_locked(&self, name: &str, locked: bool) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_layer_locked(name, locked)
+    }
+    ///Moves a layer to the given position in the drawing order, where 0 is the bottom, returning
+    ///whether the layer exists. Indices past the top place the layer on top.
+    pub fn set_layer_index(&self, name: &str, index: usize) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.set_layer_index(name, index);
+        self.queue_draw();
+        found
+    }
+    ///Gets the name of the layer an object is on, or `None` if it is not in the `DragArea`.
+    pub fn layer_of(&self, id: ItemId) -> Option<String> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.layer_of(id)
+    }
+    ///Moves an object to the top of the given layer, returning whether it is in the `DragArea`.
+    ///The layer is added on top of the others if it does not exist.
+    pub fn move_to_layer(&self, id: ItemId, layer: &str) -> bool {
+        let self_imp = imp::DragArea::from_obj(self);
+        let found = self_imp.move_to_layer(id, layer);
+        self.queue_draw();
+        found
+    }
+    ///Gets the objects for which [`Draggable::contains`] returns true at the given point, topmost
+    ///first. Objects on hidden layers are skipped, but those on locked layers are not.
+    pub fn items_at(&self, x: f64, y: f64, coordinates: Coordinates) -> Vec<ItemId> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.items_at(x, y, coordinates)
+    }
+    ///Gets the objects that lie entirely within the given rectangle according to
+    ///[`Draggable::get_limits`], topmost first. Objects on hidden layers are skipped, but those on
+    ///locked layers are not.
+    pub fn items_in_rect(&self, rect: Rectangle, coordinates: Coordinates) -> Vec<ItemId> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.items_in_rect(rect, coordinates)
     }
     ///Gets the translation being applied from scrolling, or the "location" of the viewable
-    ///"window." Always returns `(0.0, 0.0)` if scrolling is disabled.
+    ///"window," in widget pixels. Always returns `(0.0, 0.0)` if scrolling is disabled.
     pub fn get_scroll_location(&self) -> (f64, f64) {
         let self_imp = imp::DragArea::from_obj(self);
         self_imp.get_scroll_location()
     }
+    ///Sets whether a scrollable `DragArea` can be scrolled with the mouse wheel or by scrolling on
+    ///a touchpad. Holding Shift scrolls horizontally with a mouse wheel that only scrolls
+    ///vertically. This is enabled by default.
+    pub fn set_wheel_scrolling(&self, wheel_scrolling: bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_wheel_scrolling(wheel_scrolling);
+    }
+    ///Sets how many pixels one step of the mouse wheel scrolls. Mice that scroll smoothly scroll by
+    ///fractions of a step, and touchpads scroll by as many pixels as the fingers move. With Control
+    ///held, each step's worth of scrolling zooms by the same amount. This is 50.0 by default.
+    pub fn set_wheel_scroll_step(&self, wheel_scroll_step: f64) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_wheel_scroll_step(wheel_scroll_step);
+    }
+    ///Sets how much the scene is scaled, where 1.0 is its normal size, keeping the point at the
+    ///center of the `DragArea` in place if it is scrollable. The zoom is kept within the limits set
+    ///with [`set_zoom_limits`](Self::set_zoom_limits).
+    ///
+    ///# Panics
+    ///Panics if `zoom` is not finite.
+    pub fn set_zoom(&self, zoom: f64) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_zoom(zoom);
+    }
+    ///Gets how much the scene is scaled.
+    pub fn zoom(&self) -> f64 {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.zoom()
+    }
+    ///Sets the smallest and largest zoom allowed. These are 0.1 and 10.0 by default.
+    ///
+    ///# Panics
+    ///Panics if either limit is not finite or if `min` is not greater than 0 and at most `max`.
+    pub fn set_zoom_limits(&self, min: f64, max: f64) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_zoom_limits(min, max);
+    }
+    ///Sets whether the user can zoom with Control and the mouse wheel or by pinching, which keeps
+    ///the point under the pointer or between the fingers in place if the `DragArea` is
+    ///scrollable. This is disabled by default.
+    pub fn set_zoomable(&self, zoomable: bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_zoomable(zoomable);
+    }
+    ///Limits how far the `DragArea` can be scrolled or, if `scroll_limits` is `None`, allows it to
+    ///be scrolled endlessly, which is the default.
+    pub fn set_scroll_limits(&self, scroll_limits: Option<ScrollLimits>) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_scroll_limits(scroll_limits);
+    }
+    ///Gets how far the `DragArea` can be scrolled, if it is limited.
+    pub fn scroll_limits(&self) -> Option<ScrollLimits> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.scroll_limits()
+    }
+    ///Sets whether the `DragArea` can be dragged a little past its scroll limits (see
+    ///[`set_scroll_limits`](Self::set_scroll_limits)), with increasing resistance, and springs
+    ///back when released. Otherwise, it stops at the limits. This is enabled by default.
+    pub fn set_overscroll(&self, overscroll: bool) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_overscroll(overscroll);
+    }
+    ///Sets how the `DragArea` scrolls on after a fast pan and thrown objects move on or, if
+    ///`inertia` is `None`, disables this, which is the default. The `item-moved` signal for thrown
+    ///objects is emitted once they stop, and grabbing them again stops them. Disabling inertia
+    ///stops the `DragArea` and any thrown objects where they are.
+    ///
+    ///# Panics
+    ///Panics if [`Inertia::friction`] is not greater than 0.
+    pub fn set_inertia(&self, inertia: Option<Inertia>) {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.set_inertia(inertia);
+    }
+    ///Gets how the `DragArea` scrolls on after a fast pan and thrown objects move on, if they do.
+    pub fn inertia(&self) -> Option<Inertia> {
+        let self_imp = imp::DragArea::from_obj(self);
+        self_imp.inertia()
+    }
     ///Add a function to be called immediately called before every draw.
     pub fn set_pre_draw_func(
         &self,
@@ -121,4 +964,104 @@ impl DragArea {
         let self_imp = imp::DragArea::from_obj(self);
         self_imp.unset_post_draw_func();
     }
+    fn connect_item_signal<F: Fn(&Self, ItemId, f64, f64) + 'static>(
+        &self,
+        name: &str,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_local(name, false, move |values| {
+            let obj = values[0].get::<Self>().unwrap();
+            let id = ItemId(values[1].get::<u64>().unwrap());
+            let x = values[2].get::<f64>().unwrap();
+            let y = values[3].get::<f64>().unwrap();
+            f(&obj, id, x, y);
+            None
+        })
+    }
+    fn connect_point_signal<F: Fn(&Self, f64, f64) + 'static>(
+        &self,
+        name: &str,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_local(name, false, move |values| {
+            let obj = values[0].get::<Self>().unwrap();
+            let x = values[1].get::<f64>().unwrap();
+            let y = values[2].get::<f64>().unwrap();
+            f(&obj, x, y);
+            None
+        })
+    }
+    ///Connects to the `item-drag-begin` signal, emitted with an object's coordinates when it
+    ///starts being dragged, after [`Draggable::on_drag_begin`].
+    pub fn connect_item_drag_begin<F: Fn(&Self, ItemId, f64, f64) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_item_signal("item-drag-begin", f)
+    }
+    ///Connects to the `item-moved` signal, emitted with an object's new coordinates when a drag
+    ///ends, an arrow key moves it, or it is moved back inside the `DragArea` (see
+    ///[`set_keep_inside`](Self::set_keep_inside)). Moving an object with
+    ///[`set_position`](Self::set_position) or [`move_by`](Self::move_by) does not emit this.
+    pub fn connect_item_moved<F: Fn(&Self, ItemId, f64, f64) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_item_signal("item-moved", f)
+    }
+    ///Connects to the `item-activated` signal, emitted with an object's coordinates when it is
+    ///double clicked or activated with Enter.
+    pub fn connect_item_activated<F: Fn(&Self, ItemId, f64, f64) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_item_signal("item-activated", f)
+    }
+    ///Connects to the `item-removed` signal, emitted with an object's last coordinates after it
+    ///is removed and [`Draggable::on_remove`] is run. The [`ItemId`] no longer refers to anything
+    ///at this point.
+    pub fn connect_item_removed<F: Fn(&Self, ItemId, f64, f64) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_item_signal("item-removed", f)
+    }
+    ///Connects to the `selection-changed` signal, emitted each time objects are selected or
+    ///deselected, whether by pressing them, dragging a selection rectangle, Tab, removing them, or
+    ///methods such as [`set_selection`](Self::set_selection). Use
+    ///[`selection`](Self::selection) to get the new selection.
+    pub fn connect_selection_changed<F: Fn(&Self) + 'static>(&self, f: F) -> glib::SignalHandlerId {
+        self.connect_local("selection-changed", false, move |values| {
+            let obj = values[0].get::<Self>().unwrap();
+            f(&obj);
+            None
+        })
+    }
+    ///Connects to the `background-clicked` signal, emitted with the scene coordinates of a point
+    ///with no object when it is pressed and released without moving farther than GTK's drag
+    ///threshold.
+    pub fn connect_background_clicked<F: Fn(&Self, f64, f64) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_point_signal("background-clicked", f)
+    }
+    ///Connects to the `scroll-changed` signal, emitted with the new scroll location (see
+    ///[`get_scroll_location`](Self::get_scroll_location)) each time the `DragArea` is scrolled.
+    pub fn connect_scroll_changed<F: Fn(&Self, f64, f64) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_point_signal("scroll-changed", f)
+    }
+    ///Connects to the `zoom-changed` signal, emitted with the new zoom (see [`zoom`](Self::zoom))
+    ///each time the `DragArea` is zoomed.
+    pub fn connect_zoom_changed<F: Fn(&Self, f64) + 'static>(&self, f: F) -> glib::SignalHandlerId {
+        self.connect_local("zoom-changed", false, move |values| {
+            let obj = values[0].get::<Self>().unwrap();
+            let zoom = values[1].get::<f64>().unwrap();
+            f(&obj, zoom);
+            None
+        })
+    }
 }